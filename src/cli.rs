@@ -1,3 +1,8 @@
+use crate::voting::ballot::BallotKind;
+use crate::voting::counting::Method;
+use crate::voting::tie_break::TieBreak;
+use crate::voting::validity::Validity;
+use crate::voting::weighting::Weighting;
 use clap::ColorChoice;
 use clap::{Parser, Subcommand};
 
@@ -11,13 +16,58 @@ pub struct Cli {
     pub candidate_file: Option<String>,
     #[arg(short, long)]
     pub vote_count: Option<usize>,
+    /// how many seats are filled by this election - remembered for the election
+    #[arg(long)]
+    pub seats: Option<usize>,
+    /// points per rank - borda, dowdall, modified-borda, plurality or custom:12,10,8 -
+    /// remembered for the election
+    #[arg(short, long)]
+    pub weighting: Option<Weighting>,
+    /// ranked, approval, score, grades, cumulative or quadratic - only used when a new election is created
+    #[arg(short, long)]
+    pub ballot: Option<BallotKind>,
+    /// points (cumulative) or credits (quadratic) every voter can spend
+    #[arg(long)]
+    pub budget: Option<usize>,
+    /// any-valid, void-on-error, up-to-first-error, all-ranks, no-skipped-ranks or
+    /// first-pick-stands - decides which slots of a ranked paper count, only used when
+    /// a new election is created
+    #[arg(long)]
+    pub validity: Option<Validity>,
     #[command(subcommand)]
     pub command: Option<SubCommands>,
 }
 
 #[derive(Subcommand)]
 pub enum SubCommands {
+    /// enter candidates one per line - `name;party` assigns a party or list
     Candidates,
-    Result,
+    Result {
+        /// how the papers are counted - remembered for the election
+        #[arg(short, long)]
+        method: Option<Method>,
+        /// none, later-ranks, previous-round, lots, lots:<seed> or chair - remembered for the election
+        #[arg(short, long)]
+        tie_break: Option<TieBreak>,
+        /// true checks for more than half of the valid first preferences of ranked
+        /// papers and creates the runoff between the top two when nobody has it - a runoff
+        /// that already has papers is kept - remembered for the election
+        #[arg(long)]
+        majority: Option<bool>,
+        /// also count the papers of every box or batch on their own
+        #[arg(long)]
+        per_box: bool,
+    },
+    /// create the runoff between the top two when nobody has more than half of the valid
+    /// first preferences - a runoff without papers is replaced by the current top two, the
+    /// result with the majority check does the same
+    Runoff,
+    /// count the papers with every method that reads them and show where they disagree
+    Compare,
+    /// change the name of a candidate - papers already entered keep their votes
+    Rename {
+        name: String,
+        new_name: String,
+    },
     Clear,
 }
@@ -1,9 +1,13 @@
 use crate::cli::{Cli, SubCommands};
 use crate::terminal::candidate_display::{CandidateDisplay, CandidateDisplayState};
-use crate::terminal::result_display;
 use crate::terminal::voting_display::{VotingDisplay, VotingDisplayState};
-use crate::utils::load_voting;
+use crate::terminal::{comparison_display, result_display};
+use crate::utils::{load_voting, VotingSettings};
+use crate::voting::counting::Method;
+use crate::voting::majority;
+use anyhow::bail;
 use clap::Parser;
+use iter_tools::Itertools;
 use std::fs;
 
 mod terminal;
@@ -25,12 +29,21 @@ fn main() {
 }
 
 fn run(cli: Cli) -> anyhow::Result<()> {
+    let settings = VotingSettings {
+        allowed_votes: cli.vote_count,
+        seats: cli.seats,
+        weighting: cli.weighting,
+        ballot_kind: cli.ballot,
+        budget: cli.budget,
+        validity: cli.validity,
+    };
+
     let save_path = cli.save_file.unwrap_or("save.json".to_string());
     let candidate_path = cli.candidate_file.unwrap_or("candidates.txt".to_string());
 
     match cli.command {
         None => {
-            let voting = load_voting(&candidate_path, &save_path, cli.vote_count)?;
+            let voting = load_voting(&candidate_path, &save_path, settings)?;
             let mut display = VotingDisplay::new(voting);
             while display.handle_input().unwrap() != VotingDisplayState::Done {}
         }
@@ -39,9 +52,80 @@ fn run(cli: Cli) -> anyhow::Result<()> {
                 let mut display = CandidateDisplay::new(&candidate_path);
                 while display.handle_input().unwrap() != CandidateDisplayState::Done {}
             }
-            SubCommands::Result => {
-                let voting = load_voting(&candidate_path, &save_path, cli.vote_count)?;
-                result_display::display(voting);
+            SubCommands::Result {
+                method,
+                tie_break,
+                majority,
+                per_box,
+            } => {
+                let mut voting = load_voting(&candidate_path, &save_path, settings)?;
+
+                if let Some(method) = method {
+                    if !method.reads(voting.ballot_kind) {
+                        bail!(
+                            "{} can not count {} papers - use {}",
+                            method,
+                            voting.ballot_kind,
+                            Method::reading(voting.ballot_kind).iter().join(", ")
+                        )
+                    }
+
+                    voting.method = method;
+                    voting.save();
+                }
+
+                if let Some(tie_break) = tie_break {
+                    voting.tie_break = tie_break;
+                    voting.save();
+                }
+
+                if let Some(majority) = majority {
+                    if majority {
+                        majority::check(&voting)?;
+                    }
+
+                    voting.absolute_majority = majority;
+                    voting.save();
+                }
+
+                if voting.absolute_majority {
+                    if let Ok(check) = majority::check(&voting) {
+                        if check.winner.is_none()
+                            && !check.runoff.is_empty()
+                            && !majority::runoff_started(&voting)
+                        {
+                            majority::create_runoff(&mut voting, &check)?;
+                        }
+                    }
+                }
+
+                result_display::display(voting, per_box);
+            }
+            SubCommands::Runoff => {
+                let mut voting = load_voting(&candidate_path, &save_path, settings)?;
+                let check = majority::check(&voting)?;
+                majority::create_runoff(&mut voting, &check)?;
+
+                println!(
+                    "runoff between {} - {}",
+                    check
+                        .runoff
+                        .iter()
+                        .map(|candidate| &voting.candidates[*candidate].name)
+                        .join(", "),
+                    voting.runoff.unwrap_or_default()
+                );
+            }
+            SubCommands::Compare => {
+                let voting = load_voting(&candidate_path, &save_path, settings)?;
+                comparison_display::display(voting);
+            }
+            SubCommands::Rename { name, new_name } => {
+                let mut voting = load_voting(&candidate_path, &save_path, settings)?;
+                voting.rename(&name, &new_name)?;
+                voting.save();
+
+                println!("renamed {} to {}", name, new_name.trim());
             }
             SubCommands::Clear => {
                 if fs::remove_file(&candidate_path).is_ok() {
@@ -1,4 +1,4 @@
-use crate::utils::candidate::save_candidates;
+use crate::utils::candidate::{save_candidates, split_candidate_line};
 use console::{style, Term};
 use std::io::Write;
 use std::path::Path;
@@ -28,7 +28,7 @@ impl CandidateDisplay {
 
         let name = self.term.read_line()?;
 
-        return match name.as_str() {
+        match name.as_str() {
             "save" => {
                 save_candidates(&self.path, &self.candidates)?;
                 Ok(CandidateDisplayState::Done)
@@ -40,7 +40,13 @@ impl CandidateDisplay {
                     return Ok(CandidateDisplayState::Reading);
                 }
 
-                if self.candidates.contains(&name.to_string()) {
+                let (new_name, _) = split_candidate_line(name);
+
+                if self
+                    .candidates
+                    .iter()
+                    .any(|candidate| split_candidate_line(candidate).0 == new_name)
+                {
                     writeln!(self.term, "{}", style("already defined").red())?;
 
                     return Ok(CandidateDisplayState::Reading);
@@ -50,7 +56,7 @@ impl CandidateDisplay {
                 save_candidates(&self.path, &self.candidates)?;
                 Ok(CandidateDisplayState::Reading)
             }
-        };
+        }
     }
 }
 
@@ -0,0 +1,97 @@
+use crate::voting::counting::comparison::{ranking, winners, Comparison};
+use crate::voting::Voting;
+use console::style;
+use iter_tools::Itertools;
+
+pub fn display(voting: Voting) {
+    let comparison = Comparison::new(&voting);
+
+    let names = |candidates: &[usize]| {
+        candidates
+            .iter()
+            .map(|candidate| &voting.candidates[*candidate].name)
+            .join(" = ")
+    };
+
+    let method_width = comparison
+        .results
+        .iter()
+        .map(|(method, _)| method.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    let winner_width = comparison
+        .results
+        .iter()
+        .map(|(_, result)| names(&winners(result)).len())
+        .max()
+        .unwrap_or(0)
+        .max("winner".len());
+
+    println!(
+        "{}",
+        style(format!("Comparison - {} methods", comparison.results.len())).bold()
+    );
+    println!();
+    println!(
+        "{}",
+        style(format!(
+            "{:<method_width$}  {:<winner_width$}  ranking",
+            "method", "winner"
+        ))
+        .bold()
+    );
+
+    for (method, result) in &comparison.results {
+        let winner = match winners(result) {
+            winners if winners.is_empty() => "tied".to_string(),
+            winners => names(&winners),
+        };
+        let winner = format!("{:<winner_width$}", winner);
+
+        let ranking_text = result.ranking.iter().map(|group| names(group)).join(" > ");
+
+        let winner = if winners(result) == comparison.winners {
+            style(winner)
+        } else {
+            style(winner).red().bold()
+        };
+
+        let ranking_text = if ranking(result) == comparison.ranking {
+            style(ranking_text)
+        } else {
+            style(ranking_text).yellow()
+        };
+
+        println!(
+            "{:<method_width$}  {}  {}",
+            method.to_string(),
+            winner,
+            ranking_text
+        );
+    }
+
+    println!();
+
+    let disagreeing = comparison.disagreeing();
+
+    if disagreeing.is_empty() {
+        println!("{}", style("every method elects the same").green());
+    } else {
+        println!(
+            "{}",
+            style(format!(
+                "{} of {} methods elect someone else - {}",
+                disagreeing.len(),
+                comparison.results.len(),
+                disagreeing.iter().join(", ")
+            ))
+            .red()
+        );
+    }
+
+    println!(
+        "{}",
+        style("red winners and yellow rankings differ from most methods").dim()
+    );
+}
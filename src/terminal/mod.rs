@@ -2,3 +2,5 @@ pub mod candidate_display;
 pub mod voting_display;
 
 pub mod result_display;
+
+pub mod comparison_display;
@@ -1,28 +1,225 @@
+use crate::utils::format_points;
+use crate::voting::ballot::BallotKind;
+use crate::voting::counting::CountResult;
+use crate::voting::majority;
+use crate::voting::tie_break::{break_ties, TieBreaker};
 use crate::voting::Voting;
-use iter_tools::Itertools;
-use std::cmp::Ordering;
-
-pub fn display(voting: Voting) {
-    voting
-        .candidates
-        .iter()
-        .sorted_by(|a, b| {
-            let vote_dif = b.get_votes().cmp(&a.get_votes());
-            if vote_dif != Ordering::Equal {
-                vote_dif
+use console::style;
+use std::io::stdin;
+
+pub fn display(voting: Voting, per_box: bool) {
+    let mut chair = |group: &[usize]| ask_chair(&voting, group);
+    let mut ties = TieBreaker::with_chair(&voting, &mut chair);
+
+    let result = voting.method.counter().count(&voting, &mut ties);
+    let result = break_ties(result, &mut ties);
+
+    println!("{}", style(format!("Method - {}", voting.method)).bold());
+    println!(
+        "{}",
+        style(format!("Tie-break - {}", voting.tie_break)).dim()
+    );
+    if voting.ballot_kind == BallotKind::Ranked {
+        println!("{}", style(format!("Validity - {}", voting.validity)).dim());
+    }
+    if let Some(first_round) = &voting.first_round {
+        println!("{}", style(format!("Runoff of - {}", first_round)).dim());
+    }
+    println!();
+
+    display_rounds(&voting, &result);
+    display_ranking(&voting, &result.ranking);
+    println!();
+
+    display_elected(&voting, &result);
+
+    if voting.absolute_majority {
+        println!();
+        display_majority(&voting);
+    }
+
+    println!();
+    println!("{}     - blank", voting.blank());
+    println!("{}     - invalid", voting.invalid());
+
+    display_rejections(&voting);
+
+    if per_box {
+        display_batches(&voting);
+    }
+}
+
+/// every box counted on its own - ties are left as they are
+fn display_batches(voting: &Voting) {
+    for batch in voting.batches() {
+        let batch_voting = voting.batch_voting(&batch);
+        let serials = batch_voting.papers.iter().map(|paper| paper.serial);
+
+        println!();
+        println!(
+            "{}",
+            style(format!("Box - {}", batch.as_deref().unwrap_or("none"))).bold()
+        );
+        println!(
+            "{} papers - serial {} to {}",
+            batch_voting.papers.len(),
+            serials.clone().min().unwrap_or(0),
+            serials.max().unwrap_or(0)
+        );
+        println!();
+
+        display_ranking(&batch_voting, &batch_voting.count().ranking);
+        println!();
+
+        println!("{}     - blank", batch_voting.blank());
+        println!("{}     - invalid", batch_voting.invalid());
+    }
+}
+
+/// rejected papers summed per reason for the minutes
+fn display_rejections(voting: &Voting) {
+    let rejections = voting.rejections();
+
+    if rejections.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}", style("Rejected papers").bold());
+
+    for (reason, count) in rejections {
+        match reason {
+            Some(reason) => println!("{} - {}", count, reason),
+            None => println!("{} - {}", count, style("no reason given").dim()),
+        }
+    }
+}
+
+fn display_rounds(voting: &Voting, result: &CountResult) {
+    for round in &result.rounds {
+        println!("{}", style(&round.title).bold());
+
+        for (candidate, votes) in &round.tallies {
+            let name = &voting.candidates[*candidate].name;
+
+            if round.elected.contains(candidate) {
+                println!(
+                    "{} - {} {}",
+                    format_points(*votes),
+                    name,
+                    style("elected").green()
+                );
+            } else if round.eliminated.contains(candidate) {
+                println!(
+                    "{} - {} {}",
+                    format_points(*votes),
+                    name,
+                    style("eliminated").red()
+                );
             } else {
-                b.get_first_votes().cmp(&a.get_first_votes())
+                println!("{} - {}", format_points(*votes), name);
             }
-        })
-        .for_each(|voting| {
-            println!(
-                "{} | {} - {}",
-                voting.get_votes(),
-                voting.get_first_votes(),
-                voting.name,
-            )
-        });
+        }
+
+        for note in &round.notes {
+            println!("{}", style(note).dim());
+        }
+
+        println!();
+    }
+}
+
+/// prints groups of tied candidates, winners first
+fn display_ranking(voting: &Voting, ranking: &[Vec<usize>]) {
+    println!("{}", style("Ranking").bold());
+    for (place, group) in ranking.iter().enumerate() {
+        for candidate in group {
+            println!("{}. {}", place + 1, voting.candidates[*candidate].name);
+        }
+    }
+}
+
+fn display_majority(voting: &Voting) {
+    let check = match majority::check(voting) {
+        Ok(check) => check,
+        Err(err) => {
+            println!("{}", style(err).red());
+            return;
+        }
+    };
+
+    println!("{}", style("Absolute majority").bold());
+    println!(
+        "{} of {} valid first preferences needed",
+        check.needed, check.valid
+    );
+
+    for (candidate, votes) in check.first_votes.iter().take(2) {
+        println!("{} - {}", votes, voting.candidates[*candidate].name);
+    }
+
+    match check.winner {
+        Some(winner) => println!(
+            "{}",
+            style(format!(
+                "{} has the majority",
+                voting.candidates[winner].name
+            ))
+            .green()
+        ),
+        None => println!("{}", style("nobody has the majority").red()),
+    }
 
+    if let Some(runoff) = &voting.runoff {
+        println!("runoff - {}", runoff);
+    }
+}
+
+fn display_elected(voting: &Voting, result: &CountResult) {
+    if result.elected.is_empty() {
+        println!("{}", style("No winner - tied").red());
+        return;
+    }
+
+    println!("{}", style("Elected").bold());
+    for candidate in &result.elected {
+        println!("{}", style(&voting.candidates[*candidate].name).green());
+    }
+}
+
+/// lets the chair pick the tied candidates one after another - best first
+///
+/// whoever is left when the input ends stays tied
+fn ask_chair(voting: &Voting, group: &[usize]) -> Vec<Vec<usize>> {
+    let mut left = group.to_vec();
+    let mut order = vec![];
+
+    println!("{}", style("Tie - the chair decides").bold());
+
+    while left.len() > 1 {
+        for (number, candidate) in left.iter().enumerate() {
+            println!("{} - {}", number + 1, voting.candidates[*candidate].name);
+        }
+        println!("next place:");
+
+        let mut line = String::new();
+        if !matches!(stdin().read_line(&mut line), Ok(read) if read > 0) {
+            break;
+        }
+
+        match line
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|number| (1..=left.len()).contains(number))
+        {
+            Some(number) => order.push(vec![left.remove(number - 1)]),
+            None => println!("{}", style("pick one of the numbers").red()),
+        }
+    }
+
+    order.push(left);
     println!();
-    println!("{}     - invalid", voting.invalid());
+
+    order
 }
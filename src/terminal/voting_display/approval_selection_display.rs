@@ -0,0 +1,60 @@
+use crate::terminal::voting_display::{CandidateRows, EntryDisplay};
+use crate::utils::elapsed_text;
+use crate::voting::Voting;
+use console::{style, Key, Term};
+use std::io::Write;
+
+pub struct ApprovalSelectionDisplay {
+    pub rows: CandidateRows,
+}
+
+impl ApprovalSelectionDisplay {
+    pub fn new() -> ApprovalSelectionDisplay {
+        ApprovalSelectionDisplay {
+            rows: CandidateRows::new(),
+        }
+    }
+}
+
+impl EntryDisplay for ApprovalSelectionDisplay {
+    fn display(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        term.move_cursor_to(start_x, 0)?;
+        write!(term, "{}", style("Approve").bold())?;
+
+        for (index, candidate) in voting.candidates.iter().enumerate() {
+            term.move_cursor_to(start_x, index + 1)?;
+
+            let name = elapsed_text(&candidate.name, width.saturating_sub(4));
+
+            if voting.approval_selection.contains(&candidate.id) {
+                write!(term, "[x] {}", style(name).green())?;
+            } else {
+                write!(term, "[ ] {}", name)?;
+            }
+        }
+
+        self.rows.display_done(term, start_x, voting)?;
+
+        Ok(())
+    }
+
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
+        if let Some(id) = self.rows.handle_keys(key, voting) {
+            if key == &Key::Char(' ') {
+                voting.toggle_approval(id);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cursor(&self, voting: &Voting) -> (usize, usize) {
+        self.rows.cursor(voting, 1)
+    }
+}
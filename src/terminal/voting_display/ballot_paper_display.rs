@@ -1,17 +1,45 @@
-use crate::voting::ballot::BallotPaper;
-
 use crate::utils::elapsed_text;
+use crate::voting::ballot::{BallotKind, Preference, Reason, Vote};
 use crate::voting::Voting;
 use console::{style, Key, Term};
+use iter_tools::Itertools;
 use std::io::Write;
 
+/// what is being typed in the pane
+#[derive(PartialEq)]
+pub enum Input {
+    /// why the current paper was rejected
+    Reason,
+    /// the label of the box new papers come from
+    Batch,
+}
+
 pub struct BallotPaperDisplay {
     pub current_index: usize,
+    pub input: Option<(Input, String)>,
+    /// shows the results per box instead of the papers
+    pub by_batch: bool,
 }
 
 impl BallotPaperDisplay {
     pub fn new() -> BallotPaperDisplay {
-        BallotPaperDisplay { current_index: 0 }
+        BallotPaperDisplay {
+            current_index: 0,
+            input: None,
+            by_batch: false,
+        }
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// lines taken by one paper including its header and spacing
+    pub fn paper_height(voting: &Voting) -> usize {
+        match voting.ballot_kind {
+            BallotKind::Ranked => voting.allowed_votes + 2,
+            _ => 3,
+        }
     }
 
     /// returns (how many elements above center, offset)
@@ -19,7 +47,7 @@ impl BallotPaperDisplay {
         let height = term.size().0 as usize;
 
         //todo not for 4
-        let visible_papers = height / (voting.allowed_votes + 2);
+        let visible_papers = height / Self::paper_height(voting);
         let above = visible_papers / 2;
 
         (
@@ -37,35 +65,131 @@ impl BallotPaperDisplay {
     ) -> anyhow::Result<()> {
         term.move_cursor_to(start_x, 0)?;
 
+        match &self.input {
+            Some((Input::Batch, text)) => {
+                write!(term, "{}", style(format!("box: {}_", text)).yellow())?
+            }
+            _ => write!(
+                term,
+                "{}",
+                style(elapsed_text(
+                    &format!(
+                        "box {} - b to change, p per box",
+                        voting.batch.as_deref().unwrap_or("-")
+                    ),
+                    width
+                ))
+                .dim()
+            )?,
+        }
+
+        if self.by_batch {
+            return Self::display_batches(term, start_x, width, voting);
+        }
+
         let (_, offset) = self.get_list_offset(term, voting);
 
         let height = term.size().0 as usize;
 
-        let mut y = 0;
+        let mut y = 1;
 
         for (index, paper) in voting.papers[(offset)..].iter().enumerate() {
             if y > height {
                 break;
             }
 
+            let header = match &paper.batch {
+                Some(batch) => format!("paper {} - {}", paper.serial, batch),
+                None => format!("paper {}", paper.serial),
+            };
+
             term.move_cursor_to(start_x, y)?;
             if paper.disabled {
+                write!(term, "{}", style(header).dim().white())?;
+            } else {
+                write!(term, "{}", style(header))?;
+            }
+
+            let typed = match &self.input {
+                Some((Input::Reason, text)) if index + offset == self.current_index => Some(text),
+                _ => None,
+            };
+
+            if paper.blank {
+                write!(term, " {}", style("blank").dim())?;
+            }
+
+            if let Some(text) = typed {
+                write!(term, " {}", style(format!("reason: {}_", text)).yellow())?;
+            } else if let Some(reason) = &paper.reason {
+                write!(term, " {}", style(reason.to_string()).red())?;
+            } else if paper.is_rejected() && index + offset == self.current_index {
                 write!(
                     term,
-                    "{}",
-                    style(format!("paper {}", index + offset)).dim().white()
+                    " {}",
+                    style(format!("1-{} or t for a reason", Reason::COMMON.len())).dim()
+                )?;
+            }
+
+            if voting.editing == Some(index + offset) {
+                write!(term, " {}", style("editing - esc to cancel").yellow())?;
+            } else if !paper.history.is_empty() {
+                write!(
+                    term,
+                    " {}",
+                    style(format!("corrected {}x", paper.history.len())).dim()
                 )?;
-            } else {
-                write!(term, "{}", style(format!("paper {}", index + offset)))?;
             }
             y += 1;
 
-            for name in &paper.voting {
+            // (text, whether it is a mark without a candidate)
+            let lines: Vec<(String, bool)> = match &paper.voting {
+                Vote::Ranked(ranking) => ranking
+                    .iter()
+                    .map(|preference| match preference {
+                        Preference::Candidate(id) => {
+                            (voting.candidate_name(*id).to_string(), false)
+                        }
+                        Preference::Blank => ("blank".to_string(), true),
+                        Preference::Invalid => ("invalid".to_string(), true),
+                    })
+                    .collect(),
+                Vote::Approval { approved } => vec![(
+                    approved
+                        .iter()
+                        .map(|id| voting.candidate_name(*id))
+                        .join(", "),
+                    false,
+                )],
+                Vote::Score { scores } => vec![(
+                    scores
+                        .iter()
+                        .map(|(id, score)| format!("{} {}", voting.candidate_name(*id), score))
+                        .join(", "),
+                    false,
+                )],
+                Vote::Grades { grades } => vec![(
+                    grades
+                        .iter()
+                        .map(|(id, grade)| format!("{} {}", voting.candidate_name(*id), grade))
+                        .join(", "),
+                    false,
+                )],
+                Vote::Points { points } => vec![(
+                    points
+                        .iter()
+                        .map(|(id, votes)| format!("{} {}", voting.candidate_name(*id), votes))
+                        .join(", "),
+                    false,
+                )],
+            };
+
+            for (text, muted) in &lines {
                 term.move_cursor_to(start_x, y)?;
-                if paper.disabled {
-                    write!(term, "{}", style(elapsed_text(name, width)).dim().white())?;
+                if paper.disabled || *muted {
+                    write!(term, "{}", style(elapsed_text(text, width)).dim().white())?;
                 } else {
-                    write!(term, "{}", elapsed_text(name, width))?;
+                    write!(term, "{}", elapsed_text(text, width))?;
                 }
                 y += 1;
             }
@@ -76,7 +200,106 @@ impl BallotPaperDisplay {
         Ok(())
     }
 
+    /// the papers, blank and invalid counts and the leaders of every box
+    fn display_batches(
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        let mut y = 1;
+
+        for batch in voting.batches() {
+            let batch_voting = voting.batch_voting(&batch);
+            let serials = batch_voting.papers.iter().map(|paper| paper.serial);
+
+            let leading = match batch_voting.count().ranking.first() {
+                Some(group) if batch_voting.counted_papers().next().is_some() => group
+                    .iter()
+                    .map(|candidate| &voting.candidates[*candidate].name)
+                    .join(" = "),
+                _ => "-".to_string(),
+            };
+
+            let lines = [
+                style(elapsed_text(batch.as_deref().unwrap_or("no box"), width)).bold(),
+                style(elapsed_text(
+                    &format!(
+                        "{} papers - {} to {}",
+                        batch_voting.papers.len(),
+                        serials.clone().min().unwrap_or(0),
+                        serials.max().unwrap_or(0)
+                    ),
+                    width,
+                )),
+                style(elapsed_text(
+                    &format!(
+                        "{} blank - {} invalid",
+                        batch_voting.blank(),
+                        batch_voting.invalid()
+                    ),
+                    width,
+                ))
+                .dim(),
+                style(elapsed_text(&format!("leading - {}", leading), width)).green(),
+            ];
+
+            for line in lines {
+                term.move_cursor_to(start_x, y)?;
+                write!(term, "{}", line)?;
+                y += 1;
+            }
+
+            y += 1;
+        }
+
+        Ok(())
+    }
+
     pub fn handle_keys(&mut self, key: &Key, voting: &mut Voting) {
+        if let Some((input, text)) = &mut self.input {
+            match key {
+                Key::Char(char) => text.push(*char),
+                Key::Backspace => {
+                    text.pop();
+                }
+                Key::Enter => {
+                    match input {
+                        Input::Reason => {
+                            voting.set_reason(self.current_index, text.parse().ok());
+                        }
+                        Input::Batch => {
+                            let batch = text.trim();
+                            voting.batch = (!batch.is_empty()).then(|| batch.to_string());
+                        }
+                    }
+
+                    self.input = None;
+                }
+                Key::Escape => self.input = None,
+                _ => {}
+            }
+
+            return;
+        }
+
+        match key {
+            Key::Char('b') => {
+                let batch = voting.batch.clone().unwrap_or_default();
+                self.input = Some((Input::Batch, batch));
+                return;
+            }
+            Key::Char('p') => {
+                self.by_batch = !self.by_batch;
+                return;
+            }
+            _ => {}
+        }
+
+        if voting.papers.is_empty() || self.by_batch {
+            return;
+        }
+
         match key {
             Key::ArrowUp => {
                 self.current_index += voting.papers.len();
@@ -90,6 +313,24 @@ impl BallotPaperDisplay {
             Key::Del => {
                 voting.disable_vote(self.current_index);
             }
+            Key::Insert | Key::Char('r') => {
+                voting.enable_vote(self.current_index);
+            }
+            Key::Char('t') if voting.papers[self.current_index].is_rejected() => {
+                self.input = Some((Input::Reason, String::new()));
+            }
+            Key::Char('0') => {
+                voting.set_reason(self.current_index, None);
+            }
+            Key::Char(char) => {
+                let reason = char
+                    .to_digit(10)
+                    .and_then(|digit| Reason::COMMON.get((digit as usize).checked_sub(1)?));
+
+                if let Some(reason) = reason {
+                    voting.set_reason(self.current_index, Some(reason.clone()));
+                }
+            }
             _ => {}
         };
     }
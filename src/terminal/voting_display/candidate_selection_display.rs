@@ -1,4 +1,5 @@
-use crate::utils::elapsed_text;
+use crate::terminal::voting_display::EntryDisplay;
+use crate::utils::{elapsed_text, wrap_text};
 use crate::voting::Voting;
 use anyhow::anyhow;
 use console::{style, Key, Term};
@@ -19,7 +20,13 @@ impl CandidateSelectionDisplay {
             .len()
     }
 
-    pub fn display(
+    pub fn is_on_done(&self, voting: &Voting) -> bool {
+        self.current_index == voting.candidate_selections.len()
+    }
+}
+
+impl EntryDisplay for CandidateSelectionDisplay {
+    fn display(
         &self,
         term: &mut Term,
         start_x: usize,
@@ -31,6 +38,7 @@ impl CandidateSelectionDisplay {
                 &voting.candidate_selections,
                 &voting.candidates,
                 index,
+                voting.validity,
             );
 
             let y = index * 2;
@@ -56,6 +64,28 @@ impl CandidateSelectionDisplay {
             )?;
         }
 
+        // explain what the validity rule makes of the paper
+        let decision = voting.decision();
+        let rule = style(format!("rule - {}", voting.validity)).dim();
+
+        match &decision.explanation {
+            Some(explanation) => {
+                for (line, text) in wrap_text(explanation, width).into_iter().enumerate() {
+                    term.move_cursor_to(start_x, 2 * voting.allowed_votes + 1 + line)?;
+
+                    if decision.is_valid() {
+                        write!(term, "{}", style(text).yellow())?;
+                    } else {
+                        write!(term, "{}", style(text).red())?;
+                    }
+                }
+            }
+            None => {
+                term.move_cursor_to(start_x, 2 * voting.allowed_votes + 1)?;
+                write!(term, "{}", rule)?;
+            }
+        }
+
         //render done-button
         if self.is_on_done(voting) {
             term.move_cursor_to(start_x, 2 * voting.allowed_votes)?;
@@ -79,11 +109,7 @@ impl CandidateSelectionDisplay {
         Ok(())
     }
 
-    pub fn is_on_done(&self, voting: &Voting) -> bool {
-        self.current_index == voting.candidate_selections.len()
-    }
-
-    pub fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
         match (key, self.is_on_done(voting)) {
             (Key::Enter, _) => {
                 self.current_index += 1;
@@ -125,4 +151,15 @@ impl CandidateSelectionDisplay {
 
         Ok(())
     }
+
+    fn cursor(&self, voting: &Voting) -> (usize, usize) {
+        if self.is_on_done(voting) {
+            (0, 2 * voting.allowed_votes)
+        } else {
+            (
+                self.current_search_width(voting),
+                self.current_index * 2 + 1,
+            )
+        }
+    }
 }
@@ -0,0 +1,87 @@
+use crate::terminal::voting_display::{CandidateRows, EntryDisplay};
+use crate::utils::elapsed_text;
+use crate::voting::ballot::Grade;
+use crate::voting::Voting;
+use console::{style, Key, Term};
+use std::io::Write;
+
+/// width of the longest grade name
+const GRADE_WIDTH: usize = 10;
+
+pub struct GradeSelectionDisplay {
+    pub rows: CandidateRows,
+}
+
+impl GradeSelectionDisplay {
+    pub fn new() -> GradeSelectionDisplay {
+        GradeSelectionDisplay {
+            rows: CandidateRows::new(),
+        }
+    }
+}
+
+impl EntryDisplay for GradeSelectionDisplay {
+    fn display(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        term.move_cursor_to(start_x, 0)?;
+        write!(
+            term,
+            "{}",
+            style(format!(
+                "Grade 1-{} (1 = {})",
+                Grade::ALL.len(),
+                Grade::Excellent
+            ))
+            .bold()
+        )?;
+
+        for (index, candidate) in voting.candidates.iter().enumerate() {
+            term.move_cursor_to(start_x, index + 1)?;
+
+            let name = elapsed_text(&candidate.name, width.saturating_sub(GRADE_WIDTH + 1));
+
+            match voting.grade_selection.get(&candidate.id) {
+                Some(grade) => write!(
+                    term,
+                    "{} {}",
+                    style(format!("{:<GRADE_WIDTH$}", grade.to_string())).yellow(),
+                    style(name).green()
+                )?,
+                None => write!(term, "{} {}", "-".repeat(GRADE_WIDTH), name)?,
+            }
+        }
+
+        self.rows.display_done(term, start_x, voting)?;
+
+        Ok(())
+    }
+
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
+        if let Some(id) = self.rows.handle_keys(key, voting) {
+            match key {
+                Key::Char(char) => {
+                    let grade = char
+                        .to_digit(10)
+                        .and_then(|digit| Grade::ALL.get((digit as usize).checked_sub(1)?));
+
+                    if let Some(grade) = grade {
+                        voting.set_grade(id, Some(*grade));
+                    }
+                }
+                Key::Backspace => voting.set_grade(id, None),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cursor(&self, voting: &Voting) -> (usize, usize) {
+        self.rows.cursor(voting, 0)
+    }
+}
@@ -1,7 +1,12 @@
+use crate::terminal::voting_display::approval_selection_display::ApprovalSelectionDisplay;
 use crate::terminal::voting_display::ballot_paper_display::BallotPaperDisplay;
 use crate::terminal::voting_display::candidate_selection_display::CandidateSelectionDisplay;
-use crate::utils::elapsed_text;
+use crate::terminal::voting_display::grade_selection_display::GradeSelectionDisplay;
+use crate::terminal::voting_display::point_selection_display::PointSelectionDisplay;
+use crate::terminal::voting_display::score_selection_display::ScoreSelectionDisplay;
+use crate::utils::{elapsed_text, format_points};
 
+use crate::voting::ballot::BallotKind;
 use crate::voting::Voting;
 
 use console::{style, Key, Term};
@@ -10,12 +15,103 @@ use std::process::exit;
 
 pub mod candidate_selection_display;
 
+pub mod approval_selection_display;
+
+pub mod score_selection_display;
+
+pub mod grade_selection_display;
+
+pub mod point_selection_display;
+
 pub mod ballot_paper_display;
 
+/// ctrl+b records a blank paper from any pane - not while a paper is corrected
+const BLANK_KEY: char = '\u{2}';
+
+/// the middle pane where a new paper is entered
+pub trait EntryDisplay {
+    fn display(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()>;
+
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()>;
+
+    /// (x, y) of the cursor relative to the top left of the pane
+    fn cursor(&self, voting: &Voting) -> (usize, usize);
+}
+
+/// the candidate rows and the done button below them that most entry panes move through
+pub struct CandidateRows {
+    pub current_index: usize,
+}
+
+impl CandidateRows {
+    pub fn new() -> CandidateRows {
+        CandidateRows { current_index: 0 }
+    }
+
+    pub fn is_on_done(&self, voting: &Voting) -> bool {
+        self.current_index == voting.candidates.len()
+    }
+
+    /// moves between the rows and records the paper from the done button,
+    /// returns the id of the selected candidate when the key is meant for its value
+    pub fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> Option<usize> {
+        let options = voting.candidates.len() + 1;
+
+        match (key, self.is_on_done(voting)) {
+            (Key::Enter, _) | (Key::ArrowDown, _) => {
+                self.current_index += 1;
+                self.current_index %= options;
+            }
+            (Key::ArrowUp, _) => {
+                self.current_index += options - 1;
+                self.current_index %= options;
+            }
+            (Key::Char(' '), true) => {
+                voting.vote();
+            }
+            (_, false) => return Some(voting.candidates[self.current_index].id),
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn display_done(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        term.move_cursor_to(start_x, voting.candidates.len() + 2)?;
+        if self.is_on_done(voting) {
+            write!(term, "{}", style("Done").on_yellow().bold())?;
+        } else {
+            write!(term, "{}", style("Done").yellow().bold())?;
+        }
+
+        Ok(())
+    }
+
+    /// `x` is where the cursor sits on a candidate row
+    pub fn cursor(&self, voting: &Voting, x: usize) -> (usize, usize) {
+        if self.is_on_done(voting) {
+            (0, voting.candidates.len() + 2)
+        } else {
+            (x, self.current_index + 1)
+        }
+    }
+}
+
 pub struct VotingDisplay {
     voting: Voting,
 
-    candidate_selection_display: CandidateSelectionDisplay,
+    entry_display: Box<dyn EntryDisplay>,
     ballot_display: BallotPaperDisplay,
 
     term: Term,
@@ -33,10 +129,20 @@ impl VotingDisplay {
             exit(0);
         });
 
+        let entry_display: Box<dyn EntryDisplay> = match voting.ballot_kind {
+            BallotKind::Ranked => Box::new(CandidateSelectionDisplay::new()),
+            BallotKind::Approval => Box::new(ApprovalSelectionDisplay::new()),
+            BallotKind::Score => Box::new(ScoreSelectionDisplay::new()),
+            BallotKind::Grades => Box::new(GradeSelectionDisplay::new()),
+            BallotKind::Cumulative | BallotKind::Quadratic => {
+                Box::new(PointSelectionDisplay::new())
+            }
+        };
+
         VotingDisplay {
             term: Term::buffered_stdout(),
             voting,
-            candidate_selection_display: CandidateSelectionDisplay::new(),
+            entry_display,
             ballot_display: BallotPaperDisplay::new(),
             mode: VotingDisplayMode::New,
         }
@@ -47,10 +153,10 @@ impl VotingDisplay {
 
         let width = self.term.size().1 as usize;
         let width_per = width / 3;
-        let width = (width_per - 5).max(0);
+        let width = width_per.saturating_sub(5);
 
         self.display_candidates(0, width)?;
-        self.candidate_selection_display
+        self.entry_display
             .display(&mut self.term, width_per, width, &self.voting)?;
         self.ballot_display
             .display(&mut self.term, width_per * 2, width, &self.voting)?;
@@ -67,23 +173,36 @@ impl VotingDisplay {
     fn handle_key(&mut self) -> anyhow::Result<()> {
         let key = self.term.read_key()?;
 
-        match (
-            &self.mode,
-            key,
-            self.candidate_selection_display.is_on_done(&self.voting),
-        ) {
-            (VotingDisplayMode::New, Key::ArrowRight, _)
-            | (VotingDisplayMode::New, Key::ArrowLeft, _) => self.mode = VotingDisplayMode::Edit,
-            (VotingDisplayMode::Edit, Key::ArrowRight, _)
-            | (VotingDisplayMode::Edit, Key::ArrowLeft, _) => self.mode = VotingDisplayMode::New,
-
-            (VotingDisplayMode::Edit, key, _) => {
+        match (&self.mode, key) {
+            (VotingDisplayMode::Edit, key) if self.ballot_display.is_typing() => {
+                self.ballot_display.handle_keys(&key, &mut self.voting)
+            }
+
+            (_, Key::Char(BLANK_KEY)) => {
+                self.voting.vote_blank();
+            }
+
+            (VotingDisplayMode::New, Key::ArrowRight)
+            | (VotingDisplayMode::New, Key::ArrowLeft) => self.mode = VotingDisplayMode::Edit,
+            (VotingDisplayMode::Edit, Key::ArrowRight)
+            | (VotingDisplayMode::Edit, Key::ArrowLeft) => self.mode = VotingDisplayMode::New,
+
+            (VotingDisplayMode::Edit, Key::Enter) => {
+                if self.voting.edit_vote(self.ballot_display.current_index) {
+                    self.mode = VotingDisplayMode::New;
+                }
+            }
+            (VotingDisplayMode::New, Key::Escape) if self.voting.editing.is_some() => {
+                self.voting.cancel_edit()
+            }
+
+            (VotingDisplayMode::Edit, key) => {
                 self.ballot_display.handle_keys(&key, &mut self.voting)
             }
 
-            (VotingDisplayMode::New, key, _) => self
-                .candidate_selection_display
-                .handle_keys(&key, &mut self.voting)?,
+            (VotingDisplayMode::New, key) => {
+                self.entry_display.handle_keys(&key, &mut self.voting)?
+            }
         }
 
         self.voting.save();
@@ -94,31 +213,27 @@ impl VotingDisplay {
     pub fn position_cursor(&mut self, width_per: usize) -> anyhow::Result<()> {
         match self.mode {
             VotingDisplayMode::New => {
-                if self.candidate_selection_display.is_on_done(&self.voting) {
-                    self.term
-                        .move_cursor_to(width_per, 2 * self.voting.allowed_votes)?;
-                } else {
-                    self.term.move_cursor_to(
-                        width_per
-                            + self
-                                .candidate_selection_display
-                                .current_search_width(&self.voting),
-                        self.candidate_selection_display.current_index * 2 + 1,
-                    )?;
-                }
+                let (x, y) = self.entry_display.cursor(&self.voting);
+                self.term.move_cursor_to(width_per + x, y)?;
+            }
+            VotingDisplayMode::Edit if self.ballot_display.by_batch => {
+                self.term.move_cursor_to(width_per * 2, 0)?;
             }
             VotingDisplayMode::Edit => {
                 let (above, offset) = self
                     .ballot_display
                     .get_list_offset(&self.term, &self.voting);
 
+                let paper_height = BallotPaperDisplay::paper_height(&self.voting);
+
+                // the box line sits above the papers
                 if offset != 0 {
                     self.term
-                        .move_cursor_to(width_per * 2, (self.voting.allowed_votes + 2) * above)?;
+                        .move_cursor_to(width_per * 2, paper_height * above + 1)?;
                 } else {
                     self.term.move_cursor_to(
                         width_per * 2,
-                        (self.voting.allowed_votes + 2) * self.ballot_display.current_index,
+                        paper_height * self.ballot_display.current_index + 1,
                     )?;
                 }
             }
@@ -131,19 +246,32 @@ impl VotingDisplay {
         self.term.move_cursor_to(start_x, 0)?;
         writeln!(self.term, "{}", style("Candidates").bold())?;
         writeln!(self.term, "{} Invalid", self.voting.invalid())?;
+        writeln!(self.term, "{} Blank", self.voting.blank())?;
+
+        let scores = self.voting.scores();
 
         for (index, candidate) in self.voting.candidates.iter().enumerate() {
-            self.term.move_cursor_to(0, index + 2)?;
+            self.term.move_cursor_to(0, index + 3)?;
 
             write!(
                 self.term,
                 "{}|{} {}",
-                style(candidate.get_votes()).red(),
+                style(format_points(scores[index])).red(),
                 candidate.get_first_votes(),
                 elapsed_text(&candidate.name, width),
             )?;
         }
 
+        if self.voting.editing.is_none() {
+            self.term
+                .move_cursor_to(0, self.voting.candidates.len() + 4)?;
+            write!(
+                self.term,
+                "{}",
+                style(elapsed_text("ctrl+b - blank paper", width)).dim()
+            )?;
+        }
+
         Ok(())
     }
 }
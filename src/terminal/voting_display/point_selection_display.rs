@@ -0,0 +1,123 @@
+use crate::terminal::voting_display::{CandidateRows, EntryDisplay};
+use crate::utils::elapsed_text;
+use crate::voting::ballot::BallotKind;
+use crate::voting::Voting;
+use console::{style, Key, Term};
+use std::io::Write;
+
+/// width of the votes column
+const VOTES_WIDTH: usize = 9;
+
+pub struct PointSelectionDisplay {
+    pub rows: CandidateRows,
+    /// set when the last key would have exceeded the budget
+    pub refused: bool,
+}
+
+impl PointSelectionDisplay {
+    pub fn new() -> PointSelectionDisplay {
+        PointSelectionDisplay {
+            rows: CandidateRows::new(),
+            refused: false,
+        }
+    }
+
+    fn change_points(
+        &mut self,
+        voting: &mut Voting,
+        id: usize,
+        votes: impl Fn(usize) -> Option<usize>,
+    ) {
+        let current = voting.point_selection.get(&id).copied().unwrap_or(0);
+
+        if let Some(votes) = votes(current) {
+            self.refused = !voting.set_points(id, Some(votes));
+        }
+    }
+}
+
+impl EntryDisplay for PointSelectionDisplay {
+    fn display(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        let unit = match voting.ballot_kind {
+            BallotKind::Quadratic => "credits",
+            _ => "points",
+        };
+
+        term.move_cursor_to(start_x, 0)?;
+        write!(
+            term,
+            "{}",
+            style(format!(
+                "Budget {} - {} {} left",
+                voting.budget,
+                voting.remaining_budget(),
+                unit
+            ))
+            .bold()
+        )?;
+
+        for (index, candidate) in voting.candidates.iter().enumerate() {
+            term.move_cursor_to(start_x, index + 1)?;
+
+            let name = elapsed_text(&candidate.name, width.saturating_sub(VOTES_WIDTH + 1));
+
+            match voting.point_selection.get(&candidate.id) {
+                Some(votes) => {
+                    let votes = match voting.ballot_kind {
+                        BallotKind::Quadratic => {
+                            format!("{} ({})", votes, voting.ballot_kind.cost(*votes))
+                        }
+                        _ => votes.to_string(),
+                    };
+
+                    write!(
+                        term,
+                        "{} {}",
+                        style(format!("{:<VOTES_WIDTH$}", votes)).yellow(),
+                        style(name).green()
+                    )?
+                }
+                None => write!(term, "{} {}", "-".repeat(VOTES_WIDTH), name)?,
+            }
+        }
+
+        self.rows.display_done(term, start_x, voting)?;
+
+        if self.refused {
+            term.move_cursor_to(start_x, voting.candidates.len() + 3)?;
+            write!(term, "{}", style("over budget").red())?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
+        self.refused = false;
+
+        if let Some(id) = self.rows.handle_keys(key, voting) {
+            match key {
+                Key::Char('+') => self.change_points(voting, id, |current| Some(current + 1)),
+                Key::Char('-') => self.change_points(voting, id, |current| current.checked_sub(1)),
+                Key::Char(char) => {
+                    if let Some(votes) = char.to_digit(10) {
+                        self.change_points(voting, id, |_| Some(votes as usize));
+                    }
+                }
+                Key::Backspace => self.change_points(voting, id, |_| Some(0)),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cursor(&self, voting: &Voting) -> (usize, usize) {
+        self.rows.cursor(voting, 0)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::terminal::voting_display::{CandidateRows, EntryDisplay};
+use crate::utils::elapsed_text;
+use crate::voting::ballot::MAX_SCORE;
+use crate::voting::Voting;
+use console::{style, Key, Term};
+use std::io::Write;
+
+pub struct ScoreSelectionDisplay {
+    pub rows: CandidateRows,
+}
+
+impl ScoreSelectionDisplay {
+    pub fn new() -> ScoreSelectionDisplay {
+        ScoreSelectionDisplay {
+            rows: CandidateRows::new(),
+        }
+    }
+}
+
+impl EntryDisplay for ScoreSelectionDisplay {
+    fn display(
+        &self,
+        term: &mut Term,
+        start_x: usize,
+        width: usize,
+        voting: &Voting,
+    ) -> anyhow::Result<()> {
+        term.move_cursor_to(start_x, 0)?;
+        write!(term, "{}", style(format!("Score 0-{}", MAX_SCORE)).bold())?;
+
+        let stars_width = MAX_SCORE as usize + 1;
+
+        for (index, candidate) in voting.candidates.iter().enumerate() {
+            term.move_cursor_to(start_x, index + 1)?;
+
+            let name = elapsed_text(&candidate.name, width.saturating_sub(stars_width));
+
+            match voting.score_selection.get(&candidate.id) {
+                Some(score) => {
+                    let stars =
+                        "★".repeat(*score as usize) + &"☆".repeat((MAX_SCORE - score) as usize);
+                    write!(term, "{} {}", style(stars).yellow(), style(name).green())?
+                }
+                None => write!(term, "{} {}", "-".repeat(MAX_SCORE as usize), name)?,
+            }
+        }
+
+        self.rows.display_done(term, start_x, voting)?;
+
+        Ok(())
+    }
+
+    fn handle_keys(&mut self, key: &Key, voting: &mut Voting) -> anyhow::Result<()> {
+        if let Some(id) = self.rows.handle_keys(key, voting) {
+            match key {
+                Key::Char(char) => {
+                    if let Some(score) = char.to_digit(10) {
+                        voting.set_score(id, Some(score as u8));
+                    }
+                }
+                Key::Backspace => voting.set_score(id, None),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn cursor(&self, voting: &Voting) -> (usize, usize) {
+        self.rows.cursor(voting, 0)
+    }
+}
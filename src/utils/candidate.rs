@@ -1,5 +1,4 @@
 use crate::voting::candidate::Candidate;
-use iter_tools::all;
 use std::fs;
 use std::path::Path;
 
@@ -11,10 +10,27 @@ pub fn load_candidates<P: AsRef<Path>>(
 
     Ok(content
         .lines()
-        .map(|name| Candidate::new(name.to_string(), allowed_votes))
+        .map(|line| {
+            let (name, party) = split_candidate_line(line);
+
+            let mut candidate = Candidate::new(name, allowed_votes);
+            candidate.party = party;
+            candidate
+        })
         .collect())
 }
 
+/// `name;party` - the party part is optional
+pub fn split_candidate_line(line: &str) -> (String, Option<String>) {
+    match line.split_once(';') {
+        Some((name, party)) if !party.trim().is_empty() => {
+            (name.trim().to_string(), Some(party.trim().to_string()))
+        }
+        Some((name, _)) => (name.trim().to_string(), None),
+        None => (line.trim().to_string(), None),
+    }
+}
+
 pub fn save_candidates<P: AsRef<Path>>(path: P, candidates: &[String]) -> anyhow::Result<()> {
     let content = candidates.join("\n").to_string();
 
@@ -25,7 +41,7 @@ pub fn save_candidates<P: AsRef<Path>>(path: P, candidates: &[String]) -> anyhow
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::candidate::{load_candidates, save_candidates};
+    use crate::utils::candidate::{load_candidates, save_candidates, split_candidate_line};
     use iter_tools::Itertools;
     use std::env::temp_dir;
     use std::fs;
@@ -51,6 +67,41 @@ mod tests {
         )
     }
 
+    #[test]
+    fn load_candidate_reads_parties() {
+        let temp_path = temp_dir();
+
+        let candidate_path = temp_path.join("party_candidates.txt");
+
+        let _ = fs::write(&candidate_path, "huff;red\npuff\nmuff ; blue");
+
+        let candidates = load_candidates(&candidate_path, 2).unwrap();
+
+        let parties = candidates
+            .iter()
+            .map(|candidate| (candidate.name.as_str(), candidate.party.as_deref()))
+            .collect_vec();
+
+        assert_eq!(
+            parties,
+            vec![
+                ("huff", Some("red")),
+                ("puff", None),
+                ("muff", Some("blue"))
+            ]
+        )
+    }
+
+    #[test]
+    fn split_candidate_line_returns_correct() {
+        assert_eq!(split_candidate_line("huff"), ("huff".to_string(), None));
+        assert_eq!(split_candidate_line("huff; "), ("huff".to_string(), None));
+        assert_eq!(
+            split_candidate_line("huff puff;red"),
+            ("huff puff".to_string(), Some("red".to_string()))
+        );
+    }
+
     #[test]
     fn save_candidate_returns_correct() {
         let temp_path = temp_dir();
@@ -1,4 +1,8 @@
 use crate::utils::candidate::load_candidates;
+use crate::voting::ballot::BallotKind;
+use crate::voting::counting::Method;
+use crate::voting::validity::Validity;
+use crate::voting::weighting::Weighting;
 use crate::voting::Voting;
 use anyhow::{anyhow, bail};
 use std::fs;
@@ -22,25 +26,113 @@ pub fn elapsed_text(text: &str, max_length: usize) -> String {
     }
 }
 
+/// splits the text into lines of at most `max_length` at spaces - longer words are cut
+pub fn wrap_text(text: &str, max_length: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.len() + 1 + word.len() <= max_length => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(elapsed_text(word, max_length)),
+        }
+    }
+
+    lines
+}
+
+/// whole points without decimals, everything else with two
+pub fn format_points(points: f64) -> String {
+    if points.fract() == 0.0 {
+        format!("{}", points)
+    } else {
+        format!("{:.2}", points)
+    }
+}
+
+/// settings given on the command line - `None` keeps the saved or default value
+#[derive(Default)]
+pub struct VotingSettings {
+    pub allowed_votes: Option<usize>,
+    pub seats: Option<usize>,
+    pub weighting: Option<Weighting>,
+    pub ballot_kind: Option<BallotKind>,
+    pub budget: Option<usize>,
+    pub validity: Option<Validity>,
+}
+
 pub fn load_voting<P1: AsRef<Path>, P2: AsRef<Path>>(
     candidate: P1,
     save: P2,
-    allowed_votes: Option<usize>,
+    settings: VotingSettings,
 ) -> anyhow::Result<Voting> {
+    if settings.seats == Some(0) {
+        bail!("at least one seat has to be filled")
+    }
+
+    if settings.budget == Some(0) {
+        bail!("the budget has to be at least 1")
+    }
+
     if let Ok(content) = fs::read_to_string(&save) {
-        let voting = Voting::load(content)
+        let mut voting = Voting::load(content)
             .map_err(|_| anyhow!("Invalid save file - {:?} ", save.as_ref()))?;
 
-        if allowed_votes.is_some() && voting.allowed_votes != allowed_votes.unwrap() {
+        if settings.allowed_votes.is_some()
+            && voting.allowed_votes != settings.allowed_votes.unwrap()
+        {
             bail!("you allowed votes have changed")
         }
 
+        if settings.ballot_kind.is_some() && voting.ballot_kind != settings.ballot_kind.unwrap() {
+            bail!("your ballot kind has changed")
+        }
+
+        if settings.budget.is_some() && voting.budget != settings.budget.unwrap() {
+            bail!("your budget has changed")
+        }
+
+        if settings.validity.is_some() && voting.validity != settings.validity.unwrap() {
+            bail!("your validity rule has changed")
+        }
+
+        let changed = settings.seats.is_some_and(|seats| seats != voting.seats)
+            || settings
+                .weighting
+                .as_ref()
+                .is_some_and(|weighting| *weighting != voting.weighting);
+
+        if let Some(seats) = settings.seats {
+            voting.seats = seats;
+        }
+
+        if let Some(weighting) = settings.weighting {
+            voting.weighting = weighting;
+        }
+
+        // seats and weighting are remembered for the election
+        if changed {
+            voting.save();
+        }
+
         Ok(voting)
     } else {
-        let allowed_votes = allowed_votes.unwrap_or(2);
+        let allowed_votes = settings.allowed_votes.unwrap_or(2);
 
         match load_candidates(&candidate, allowed_votes) {
-            Ok(candidates) => Ok(Voting::new(candidates, save, allowed_votes)?),
+            Ok(candidates) => {
+                let mut voting = Voting::new(candidates, save, allowed_votes)?;
+                voting.seats = settings.seats.unwrap_or(voting.seats);
+                voting.weighting = settings.weighting.unwrap_or_default();
+                voting.ballot_kind = settings.ballot_kind.unwrap_or_default();
+                voting.method = Method::default_for(voting.ballot_kind);
+                voting.budget = settings.budget.unwrap_or(voting.budget);
+                voting.validity = settings.validity.unwrap_or_default();
+
+                Ok(voting)
+            }
             Err(_) => bail!(
                 "could not file {:?} or {:?}",
                 candidate.as_ref(),
@@ -52,7 +144,12 @@ pub fn load_voting<P1: AsRef<Path>, P2: AsRef<Path>>(
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{elapsed_text, get_fitting_names, load_voting};
+    use crate::utils::{
+        elapsed_text, format_points, get_fitting_names, load_voting, wrap_text, VotingSettings,
+    };
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::counting::Method;
+    use crate::voting::weighting::Weighting;
     use iter_tools::Itertools;
     use std::env::temp_dir;
     use std::fs;
@@ -106,6 +203,12 @@ mod tests {
         assert_eq!(elapsed_text("huff huff", 4), "h...".to_string());
     }
 
+    #[test]
+    fn format_points_returns_correct() {
+        assert_eq!(format_points(3.0), "3".to_string());
+        assert_eq!(format_points(1.0 / 3.0), "0.33".to_string());
+    }
+
     #[test]
     fn load_vote_from_candidates() {
         let temp_path = temp_dir();
@@ -114,7 +217,12 @@ mod tests {
 
         let _ = fs::write(&candidate_path, "huff\npuff\nmuff");
 
-        let voting = load_voting(&candidate_path, "", Some(2)).unwrap();
+        let settings = VotingSettings {
+            allowed_votes: Some(2),
+            ..VotingSettings::default()
+        };
+
+        let voting = load_voting(&candidate_path, "", settings).unwrap();
 
         let candidate_names = voting
             .candidates
@@ -136,10 +244,18 @@ mod tests {
 
         let _ = fs::write(
             &save_path,
-            r#"{"candidate_selections":[{"search_text":"","selected_preview":0,"header":"First"},{"search_text":"","selected_preview":0,"header":"Second"}],"candidates":[{"name":"huff","votes":[0,0]}],"papers":[],"invalid_vote_count":1,"allowed_votes":2,"save_path":"save.json"}"#,
+            r#"{"candidate_selections":[{"search_text":"","selected_preview":0,"header":"First"},{"search_text":"","selected_preview":0,"header":"Second"}],"candidates":[{"name":"huff","votes":[0,0]}],"papers":[],"invalid_vote_count":1,"allowed_votes":2,"save_path":"SAVE_PATH"}"#
+                .replace("SAVE_PATH", save_path.to_str().unwrap()),
         );
 
-        let voting = load_voting("", &save_path, Some(2)).unwrap();
+        let settings = VotingSettings {
+            allowed_votes: Some(2),
+            seats: Some(3),
+            weighting: Some(Weighting::Dowdall),
+            ..VotingSettings::default()
+        };
+
+        let voting = load_voting("", &save_path, settings).unwrap();
 
         let candidate_names = voting
             .candidates
@@ -147,6 +263,67 @@ mod tests {
             .map(|candidate| candidate.name.clone())
             .collect_vec();
 
-        assert_eq!(candidate_names, vec!["huff".to_string()])
+        assert_eq!(candidate_names, vec!["huff".to_string()]);
+        assert_eq!(voting.seats, 3);
+        assert_eq!(voting.weighting, Weighting::Dowdall);
+
+        let saved = load_voting("", &save_path, VotingSettings::default()).unwrap();
+        assert_eq!(saved.seats, 3);
+        assert_eq!(saved.weighting, Weighting::Dowdall);
+    }
+
+    #[test]
+    fn load_approval_vote_from_candidates() {
+        let temp_path = temp_dir();
+
+        let candidate_path = temp_path.join("approval_candidates.txt");
+
+        let _ = fs::write(&candidate_path, "huff\npuff");
+
+        let settings = VotingSettings {
+            ballot_kind: Some(BallotKind::Approval),
+            ..VotingSettings::default()
+        };
+
+        let voting = load_voting(&candidate_path, "", settings).unwrap();
+
+        assert_eq!(voting.ballot_kind, BallotKind::Approval);
+        assert_eq!(voting.method, Method::Approval);
+    }
+
+    #[test]
+    fn load_quadratic_vote_from_candidates() {
+        let temp_path = temp_dir();
+
+        let candidate_path = temp_path.join("quadratic_candidates.txt");
+
+        let _ = fs::write(&candidate_path, "roof\npark");
+
+        let settings = VotingSettings {
+            ballot_kind: Some(BallotKind::Quadratic),
+            budget: Some(25),
+            ..VotingSettings::default()
+        };
+
+        let voting = load_voting(&candidate_path, "", settings).unwrap();
+
+        assert_eq!(voting.budget, 25);
+        assert_eq!(voting.method, Method::Points);
+
+        let settings = VotingSettings {
+            budget: Some(0),
+            ..VotingSettings::default()
+        };
+
+        assert!(load_voting(&candidate_path, "", settings).is_err());
+    }
+
+    #[test]
+    fn wrap_text_returns_correct() {
+        assert_eq!(
+            wrap_text("rank 2 is empty - every rank", 12),
+            vec!["rank 2 is", "empty -", "every rank"]
+        );
+        assert!(wrap_text("", 12).is_empty());
     }
 }
@@ -1,32 +1,450 @@
-use serde::{Deserialize, Serialize};
+use anyhow::bail;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Deserialize, Serialize, Eq, PartialEq, Debug)]
+/// what a voter marks on their paper
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub enum BallotKind {
+    /// a fixed number of ranked slots
+    #[default]
+    Ranked,
+    /// any number of approved candidates
+    Approval,
+    /// 0 to `MAX_SCORE` stars per candidate
+    Score,
+    /// a grade from excellent to reject per candidate
+    Grades,
+    /// a budget of points spread over the options
+    Cumulative,
+    /// a budget of credits where n votes for one option cost n²
+    Quadratic,
+}
+
+impl BallotKind {
+    /// the budget used by casting `votes` for one option
+    pub fn cost(&self, votes: usize) -> usize {
+        match self {
+            BallotKind::Quadratic => votes * votes,
+            _ => votes,
+        }
+    }
+}
+
+pub const MAX_SCORE: u8 = 5;
+
+/// majority judgment grades, best first
+#[derive(Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy)]
+pub enum Grade {
+    Excellent,
+    VeryGood,
+    Good,
+    Acceptable,
+    Poor,
+    Reject,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 6] = [
+        Grade::Excellent,
+        Grade::VeryGood,
+        Grade::Good,
+        Grade::Acceptable,
+        Grade::Poor,
+        Grade::Reject,
+    ];
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Grade::Excellent => write!(f, "Excellent"),
+            Grade::VeryGood => write!(f, "Very good"),
+            Grade::Good => write!(f, "Good"),
+            Grade::Acceptable => write!(f, "Acceptable"),
+            Grade::Poor => write!(f, "Poor"),
+            Grade::Reject => write!(f, "Reject"),
+        }
+    }
+}
+
+impl FromStr for BallotKind {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(match text.trim().to_lowercase().as_str() {
+            "ranked" => BallotKind::Ranked,
+            "approval" => BallotKind::Approval,
+            "score" => BallotKind::Score,
+            "grades" => BallotKind::Grades,
+            "cumulative" => BallotKind::Cumulative,
+            "quadratic" => BallotKind::Quadratic,
+            text => bail!("unknown ballot kind - {}", text),
+        })
+    }
+}
+
+impl Display for BallotKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BallotKind::Ranked => write!(f, "ranked"),
+            BallotKind::Approval => write!(f, "approval"),
+            BallotKind::Score => write!(f, "score"),
+            BallotKind::Grades => write!(f, "grades"),
+            BallotKind::Cumulative => write!(f, "cumulative"),
+            BallotKind::Quadratic => write!(f, "quadratic"),
+        }
+    }
+}
+
+/// one ranked slot of a paper
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum Preference {
+    /// the id of the picked candidate
+    Candidate(usize),
+    /// the slot was left empty
+    Blank,
+    /// the pick did not match a candidate or repeated an earlier one
+    Invalid,
+}
+
+impl Preference {
+    /// the id of the picked candidate, `None` for blank and invalid slots
+    pub fn candidate(&self) -> Option<usize> {
+        match self {
+            Preference::Candidate(id) => Some(*id),
+            Preference::Blank | Preference::Invalid => None,
+        }
+    }
+}
+
+/// the marks of one paper - candidates are referred to by their id
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone)]
+#[serde(untagged)]
+pub enum Vote {
+    Ranked(Vec<Preference>),
+    Approval {
+        approved: BTreeSet<usize>,
+    },
+    Score {
+        #[serde(deserialize_with = "id_keys")]
+        scores: BTreeMap<usize, u8>,
+    },
+    Grades {
+        #[serde(deserialize_with = "id_keys")]
+        grades: BTreeMap<usize, Grade>,
+    },
+    Points {
+        #[serde(deserialize_with = "id_keys")]
+        points: BTreeMap<usize, usize>,
+    },
+}
+
+/// json keys are text - untagged enums do not turn them back into ids on their own
+fn id_keys<'de, D, T>(deserializer: D) -> Result<BTreeMap<usize, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    BTreeMap::<String, T>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| Ok((key.parse().map_err(D::Error::custom)?, value)))
+        .collect()
+}
+
+impl Vote {
+    /// nothing was marked on the paper
+    pub fn is_blank(&self) -> bool {
+        match self {
+            Vote::Ranked(ranking) => ranking
+                .iter()
+                .all(|preference| *preference == Preference::Blank),
+            Vote::Approval { approved } => approved.is_empty(),
+            Vote::Score { scores } => scores.is_empty(),
+            Vote::Grades { grades } => grades.is_empty(),
+            Vote::Points { points } => points.is_empty(),
+        }
+    }
+
+    /// the ranked slots, empty for unranked papers
+    pub fn ranking(&self) -> &[Preference] {
+        match self {
+            Vote::Ranked(ranking) => ranking,
+            _ => &[],
+        }
+    }
+
+    /// the approved candidates, empty for unapproved papers
+    pub fn approved(&self) -> Vec<usize> {
+        match self {
+            Vote::Approval { approved } => approved.iter().copied().collect(),
+            _ => vec![],
+        }
+    }
+
+    /// the scored candidates, empty for unscored papers - missing candidates scored 0
+    pub fn scores(&self) -> Vec<(usize, u8)> {
+        match self {
+            Vote::Score { scores } => scores.iter().map(|(id, score)| (*id, *score)).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// the graded candidates, empty for ungraded papers - missing candidates are rejected
+    pub fn grades(&self) -> Vec<(usize, Grade)> {
+        match self {
+            Vote::Grades { grades } => grades.iter().map(|(id, grade)| (*id, *grade)).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// the votes given to each option, empty for papers without a budget
+    pub fn points(&self) -> Vec<(usize, usize)> {
+        match self {
+            Vote::Points { points } => points.iter().map(|(id, votes)| (*id, *votes)).collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// why a paper was rejected - recorded in the minutes
+#[derive(Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Reason {
+    Illegible,
+    Duplicate,
+    IdentifyingMark,
+    EnteredTwice,
+    /// a reason typed by the teller
+    Other(String),
+}
+
+impl Reason {
+    /// the reasons a teller can pick without typing
+    pub const COMMON: [Reason; 4] = [
+        Reason::Illegible,
+        Reason::Duplicate,
+        Reason::IdentifyingMark,
+        Reason::EnteredTwice,
+    ];
+}
+
+impl FromStr for Reason {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        if text.is_empty() {
+            bail!("the reason is empty")
+        }
+
+        Ok(Reason::COMMON
+            .into_iter()
+            .find(|reason| reason.to_string().eq_ignore_ascii_case(text))
+            .unwrap_or_else(|| Reason::Other(text.to_string())))
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Illegible => write!(f, "illegible"),
+            Reason::Duplicate => write!(f, "duplicate"),
+            Reason::IdentifyingMark => write!(f, "identifying mark"),
+            Reason::EnteredTwice => write!(f, "entered twice"),
+            Reason::Other(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone)]
 pub struct BallotPaper {
-    pub voting: Vec<String>,
+    /// the running number of the paper, starting at 1
+    #[serde(default)]
+    pub serial: usize,
+    /// the box or batch the paper came in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<String>,
+    pub voting: Vote,
     pub disabled: bool,
     pub invalid: bool,
+    /// nothing was marked - counted apart from invalid papers
+    #[serde(default)]
+    pub blank: bool,
+    /// why the paper is invalid or disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<Reason>,
+    /// earlier versions of the paper before it was corrected, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<Vote>,
 }
 
 impl BallotPaper {
-    pub fn new(voting: Vec<String>, invalid: bool) -> BallotPaper {
+    /// a paper without any marks is blank rather than invalid
+    pub fn new(voting: Vote, invalid: bool) -> BallotPaper {
+        let blank = voting.is_blank();
+
         BallotPaper {
+            serial: 0,
+            batch: None,
             voting,
             disabled: false,
-            invalid,
+            invalid: invalid && !blank,
+            blank,
+            reason: None,
+            history: vec![],
+        }
+    }
+
+    /// invalid or disabled - such papers are not counted
+    pub fn is_rejected(&self) -> bool {
+        self.invalid || self.disabled
+    }
+
+    /// replaces the marks and keeps the previous ones in the history
+    pub fn correct(&mut self, voting: Vote, invalid: bool) {
+        let previous = std::mem::replace(&mut self.voting, voting);
+
+        self.history.push(previous);
+        self.blank = self.voting.is_blank();
+        self.invalid = invalid && !self.blank;
+
+        if !self.is_rejected() {
+            self.reason = None;
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::voting::ballot::BallotPaper;
+    use crate::voting::ballot::{BallotKind, BallotPaper, Grade, Preference, Reason, Vote};
+    use std::collections::BTreeSet;
 
     #[test]
     fn constructor() {
-        let paper = BallotPaper::new(vec!["test".to_string()], false);
+        let paper = BallotPaper::new(Vote::Ranked(vec![Preference::Candidate(2)]), false);
+
+        assert_eq!(paper.voting, Vote::Ranked(vec![Preference::Candidate(2)]));
+        assert!(!paper.invalid);
+        assert!(!paper.disabled);
+    }
+
+    #[test]
+    fn correct() {
+        let mut paper = BallotPaper::new(Vote::Ranked(vec![Preference::Invalid]), true);
+
+        paper.correct(Vote::Ranked(vec![Preference::Candidate(1)]), false);
+
+        assert_eq!(paper.voting, Vote::Ranked(vec![Preference::Candidate(1)]));
+        assert_eq!(paper.history, vec![Vote::Ranked(vec![Preference::Invalid])]);
+        assert!(!paper.invalid);
+    }
+
+    #[test]
+    fn blank() {
+        let paper = BallotPaper::new(Vote::Ranked(vec![Preference::Blank; 3]), true);
 
-        assert_eq!(paper.voting, vec!["test".to_string()]);
-        assert_eq!(paper.invalid, false);
-        assert_eq!(paper.disabled, false);
+        assert!(paper.blank);
+        assert!(!paper.invalid);
+
+        let paper = BallotPaper::new(
+            Vote::Ranked(vec![Preference::Blank, Preference::Invalid]),
+            true,
+        );
+
+        assert!(!paper.blank);
+        assert!(paper.invalid);
+    }
+
+    #[test]
+    fn parse_reason() {
+        assert_eq!(
+            "Identifying mark".parse::<Reason>().unwrap(),
+            Reason::IdentifyingMark
+        );
+        assert_eq!(
+            " torn ".parse::<Reason>().unwrap(),
+            Reason::Other("torn".to_string())
+        );
+        assert!("".parse::<Reason>().is_err());
+    }
+
+    #[test]
+    fn reads_ranked_and_approval_papers() {
+        let ranked = BallotPaper::new(
+            Vote::Ranked(vec![
+                Preference::Candidate(3),
+                Preference::Blank,
+                Preference::Invalid,
+            ]),
+            false,
+        );
+
+        let json = serde_json::to_string(&ranked).unwrap();
+        assert!(json.contains(r#"{"kind":"candidate","id":3}"#));
+        assert!(json.contains(r#"{"kind":"blank"}"#));
+
+        let read: BallotPaper = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(read, ranked);
+        assert_eq!(read.voting.ranking()[0].candidate(), Some(3));
+        assert_eq!(read.voting.ranking()[1].candidate(), None);
+        assert!(read.voting.approved().is_empty());
+
+        let approval = BallotPaper::new(
+            Vote::Approval {
+                approved: BTreeSet::from([4, 1]),
+            },
+            false,
+        );
+
+        let approval: BallotPaper =
+            serde_json::from_str(&serde_json::to_string(&approval).unwrap()).unwrap();
+
+        assert!(approval.voting.ranking().is_empty());
+        assert_eq!(approval.voting.approved(), [1, 4]);
+    }
+
+    #[test]
+    fn reads_score_papers() {
+        let paper: BallotPaper = serde_json::from_str(
+            r#"{"voting":{"scores":{"0":5,"1":0}},"disabled":false,"invalid":false}"#,
+        )
+        .unwrap();
+
+        assert!(paper.voting.ranking().is_empty());
+        assert!(paper.voting.approved().is_empty());
+        assert_eq!(paper.voting.scores(), vec![(0, 5), (1, 0)]);
+    }
+
+    #[test]
+    fn reads_grade_papers() {
+        let paper: BallotPaper = serde_json::from_str(
+            r#"{"voting":{"grades":{"2":"VeryGood"}},"disabled":false,"invalid":false}"#,
+        )
+        .unwrap();
+
+        assert!(paper.voting.scores().is_empty());
+        assert_eq!(paper.voting.grades(), vec![(2, Grade::VeryGood)]);
+    }
+
+    #[test]
+    fn quadratic_votes_cost_squares() {
+        assert_eq!(BallotKind::Cumulative.cost(3), 3);
+        assert_eq!(BallotKind::Quadratic.cost(3), 9);
+        assert_eq!(BallotKind::Quadratic.cost(0), 0);
+    }
+
+    #[test]
+    fn parse_kind() {
+        assert_eq!(
+            "Approval".parse::<BallotKind>().unwrap(),
+            BallotKind::Approval
+        );
+        assert!("huff".parse::<BallotKind>().is_err());
     }
 }
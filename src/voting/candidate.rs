@@ -1,16 +1,24 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Eq, PartialEq, Debug)]
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone)]
 pub struct Candidate {
+    /// stays the same when the name is changed - papers refer to candidates by it
+    #[serde(default)]
+    pub id: usize,
     pub name: String,
     pub votes: Vec<usize>,
+    /// the party or list the candidate stands for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub party: Option<String>,
 }
 
 impl Candidate {
     pub fn new(name: String, size: usize) -> Candidate {
         Candidate {
+            id: 0,
             name,
             votes: vec![0; size],
+            party: None,
         }
     }
 
@@ -22,17 +30,7 @@ impl Candidate {
     }
 
     pub fn get_first_votes(&self) -> usize {
-        *self.votes.get(0).unwrap()
-    }
-
-    pub fn get_votes(&self) -> usize {
-        let size = self.votes.len();
-
-        self.votes
-            .iter()
-            .enumerate()
-            .map(|(index, count)| (size - index) * count)
-            .sum()
+        *self.votes.first().unwrap()
     }
 }
 
@@ -52,7 +50,7 @@ mod tests {
         candidate.vote(0);
 
         assert_eq!(candidate.get_first_votes(), 1);
-        assert_eq!(candidate.get_votes(), 1 + 1 + 3 + 4);
+        assert_eq!(candidate.votes, vec![1, 1, 0, 2]);
     }
 
     #[test]
@@ -67,6 +65,6 @@ mod tests {
         candidate.vote(3);
 
         assert_eq!(candidate.get_first_votes(), 0);
-        assert_eq!(candidate.get_votes(), 3 + 1);
+        assert_eq!(candidate.votes, vec![0, 1, 0, 1]);
     }
 }
@@ -1,8 +1,12 @@
 use crate::utils::get_fitting_names;
 use crate::voting::candidate::Candidate;
+use crate::voting::validity::{Mark, Validity};
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Eq, PartialEq, Debug)]
+/// stands in for an invalid slot of a paper loaded to be corrected - it never matches a candidate
+const INVALID_TEXT: &str = "(invalid)";
+
+#[derive(Deserialize, Serialize, Eq, PartialEq, Debug, Clone)]
 pub struct CandidateSelection {
     pub search_text: String,
     pub selected_preview: usize,
@@ -23,24 +27,65 @@ impl CandidateSelection {
         self.selected_preview = 0;
     }
 
-    pub fn is_valid(
+    /// fills in the name so that the candidate is the selected preview
+    pub fn select(&mut self, name: &str, candidates: &[Candidate]) {
+        self.search_text = name.to_string();
+        self.selected_preview = self
+            .possible_candidates_names(candidates)
+            .iter()
+            .position(|possible| possible == name)
+            .unwrap_or(0);
+    }
+
+    /// keeps the slot invalid until something else is entered
+    pub fn mark_invalid(&mut self) {
+        self.search_text = INVALID_TEXT.to_string();
+        self.selected_preview = 0;
+    }
+
+    /// what the slot holds - a candidate picked on another slot as well is repeated
+    pub fn mark(
         &self,
         others: &[CandidateSelection],
         candidates: &[Candidate],
         own_index: usize,
-    ) -> bool {
+    ) -> Mark {
+        if self.search_text.is_empty() {
+            return Mark::Blank;
+        }
+
         let candidate = self.selected_candidate(candidates);
 
-        if others
-            .iter()
-            .enumerate()
-            .filter(|(other_index, _)| own_index != *other_index)
-            .any(|(_, other)| other.selected_candidate(candidates) == candidate)
-        {
-            return false;
+        if candidate.is_none() {
+            return Mark::Unmatched;
+        }
+
+        let picked = |others: &[CandidateSelection]| {
+            others
+                .iter()
+                .any(|other| other.selected_candidate(candidates) == candidate)
+        };
+
+        if picked(&others[..own_index]) {
+            return Mark::Repeated;
         }
 
-        self.search_text.is_empty() || !self.possible_candidates_names(candidates).is_empty()
+        if picked(&others[own_index + 1..]) {
+            return Mark::RepeatedLater;
+        }
+
+        Mark::Candidate
+    }
+
+    /// whether the slot follows the validity rule of the election
+    pub fn is_valid(
+        &self,
+        others: &[CandidateSelection],
+        candidates: &[Candidate],
+        own_index: usize,
+        validity: Validity,
+    ) -> bool {
+        !validity.decide(&marks(others, candidates)).faulty[own_index]
     }
 
     pub fn possible_candidates_names(&self, candidates: &[Candidate]) -> Vec<String> {
@@ -54,7 +99,7 @@ impl CandidateSelection {
     }
 
     pub fn selected_candidate(&self, candidates: &[Candidate]) -> Option<String> {
-        if self.search_text.is_empty() {
+        if self.search_text.is_empty() || self.search_text == INVALID_TEXT {
             return None;
         }
 
@@ -64,25 +109,40 @@ impl CandidateSelection {
     }
 }
 
+/// the marks of every slot of a paper
+pub fn marks(selections: &[CandidateSelection], candidates: &[Candidate]) -> Vec<Mark> {
+    selections
+        .iter()
+        .enumerate()
+        .map(|(index, selection)| selection.mark(selections, candidates, index))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::voting::ballot::BallotPaper;
     use crate::voting::candidate::Candidate;
-    use crate::voting::candidate_selection::CandidateSelection;
+    use crate::voting::candidate_selection::{marks, CandidateSelection};
+    use crate::voting::validity::{Mark, Validity};
 
     fn get_candidates() -> [Candidate; 3] {
         [
             Candidate {
+                id: 0,
                 name: "time test".to_string(),
                 votes: vec![],
+                party: None,
             },
             Candidate {
+                id: 1,
                 name: "test".to_string(),
                 votes: vec![],
+                party: None,
             },
             Candidate {
+                id: 2,
                 name: "ok i think".to_string(),
                 votes: vec![],
+                party: None,
             },
         ]
     }
@@ -120,7 +180,7 @@ mod tests {
 
         let selections = [selection_a, selection_b];
 
-        assert!(selections[0].is_valid(&selections, &get_candidates(), 0,));
+        assert!(selections[0].is_valid(&selections, &get_candidates(), 0, Validity::AnyValid));
     }
 
     #[test]
@@ -133,7 +193,70 @@ mod tests {
 
         let selections = [selection_a, selection_b];
 
-        assert!(!selections[0].is_valid(&selections, &get_candidates(), 0,));
+        assert!(!selections[0].is_valid(&selections, &get_candidates(), 0, Validity::AnyValid));
+    }
+
+    #[test]
+    fn first_pick_stands() {
+        let mut selection_a = CandidateSelection::new("header".to_string());
+        selection_a.search_text = "test".to_string();
+
+        let mut selection_b = CandidateSelection::new("header2".to_string());
+        selection_b.search_text = "test".to_string();
+
+        let selections = [selection_a, selection_b];
+
+        assert_eq!(
+            marks(&selections, &get_candidates()),
+            vec![Mark::RepeatedLater, Mark::Repeated]
+        );
+        assert!(selections[0].is_valid(
+            &selections,
+            &get_candidates(),
+            0,
+            Validity::FirstPickStands
+        ));
+        assert!(!selections[1].is_valid(
+            &selections,
+            &get_candidates(),
+            1,
+            Validity::FirstPickStands
+        ));
+    }
+
+    #[test]
+    fn follows_validity() {
+        let mut selections = [
+            CandidateSelection::new("header".to_string()),
+            CandidateSelection::new("header2".to_string()),
+            CandidateSelection::new("header3".to_string()),
+        ];
+        selections[0].search_text = "nobody".to_string();
+        selections[1].search_text = "ok".to_string();
+
+        assert_eq!(
+            marks(&selections, &get_candidates()),
+            vec![Mark::Unmatched, Mark::Candidate, Mark::Blank]
+        );
+        assert!(selections[1].is_valid(&selections, &get_candidates(), 1, Validity::AnyValid));
+        assert!(!selections[1].is_valid(
+            &selections,
+            &get_candidates(),
+            1,
+            Validity::UpToFirstError
+        ));
+    }
+
+    #[test]
+    fn invalid_mark_never_matches() {
+        let mut candidates = Vec::from(get_candidates());
+        candidates.push(Candidate::new("(invalid) huff".to_string(), 1));
+
+        let mut selections = vec![CandidateSelection::new("header".to_string())];
+        selections[0].mark_invalid();
+
+        assert_eq!(selections[0].selected_candidate(&candidates), None);
+        assert_eq!(marks(&selections, &candidates), vec![Mark::Unmatched]);
     }
 
     #[test]
@@ -163,4 +286,16 @@ mod tests {
         let name = selection.selected_candidate(&get_candidates());
         assert_eq!(name, None);
     }
+
+    #[test]
+    fn select() {
+        let mut selection = CandidateSelection::new("header".to_string());
+
+        selection.select("test", &get_candidates());
+
+        assert_eq!(
+            selection.selected_candidate(&get_candidates()),
+            Some("test".to_string())
+        );
+    }
 }
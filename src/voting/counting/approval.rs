@@ -0,0 +1,71 @@
+use crate::voting::counting::{elect_top, elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+
+/// ranks by how many papers approve of each candidate
+pub struct Approval;
+
+impl CountingMethod for Approval {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let tallies: Vec<(usize, f64)> = voting
+            .approvals()
+            .iter()
+            .enumerate()
+            .map(|(candidate, approvals)| (candidate, *approvals as f64))
+            .collect();
+
+        let ranking = elimination_ranking(&tallies, &[]);
+
+        let round = Round {
+            title: "Approvals".to_string(),
+            tallies: ranking
+                .iter()
+                .flatten()
+                .map(|candidate| tallies[*candidate])
+                .collect(),
+            ..Round::default()
+        };
+
+        CountResult {
+            rounds: vec![round],
+            elected: elect_top(&ranking, voting.seats),
+            ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::approval::Approval;
+    use crate::voting::counting::CountingMethod;
+    use crate::voting::tie_break::TieBreaker;
+    use crate::voting::Voting;
+
+    #[test]
+    fn most_approvals_win() {
+        let candidates = vec![
+            Candidate::new("huff".to_string(), 1),
+            Candidate::new("puff".to_string(), 1),
+            Candidate::new("muff".to_string(), 1),
+        ];
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+        voting.ballot_kind = BallotKind::Approval;
+        voting.seats = 2;
+
+        voting.toggle_approval(0);
+        voting.toggle_approval(1);
+        voting.vote();
+
+        voting.toggle_approval(1);
+        voting.toggle_approval(2);
+        voting.vote();
+
+        let result = Approval.count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.ranking, vec![vec![1], vec![0, 2]]);
+        assert_eq!(result.elected, vec![1]);
+        assert_eq!(result.rounds[0].tallies[0], (1, 2.0));
+    }
+}
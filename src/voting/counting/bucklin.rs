@@ -0,0 +1,142 @@
+use crate::voting::counting::{elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// adds the next preference level until somebody holds a majority of the papers
+pub struct Bucklin;
+
+pub struct BucklinResult {
+    /// (candidate index, cumulative votes) per level, most votes first
+    pub levels: Vec<Vec<(usize, usize)>>,
+    /// votes needed for a majority
+    pub majority: usize,
+    /// the level the majority was reached at, starting with 0
+    pub majority_level: Option<usize>,
+    pub winner: Option<usize>,
+}
+
+/// `level_votes[candidate][level]` - how often a candidate was ranked at that level
+///
+/// without a majority at any level the highest final total wins
+pub fn count(level_votes: &[Vec<usize>], papers: usize) -> BucklinResult {
+    let majority = papers / 2 + 1;
+    let level_count = level_votes
+        .iter()
+        .map(|votes| votes.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut levels = vec![];
+    let mut majority_level = None;
+
+    for level in 0..level_count {
+        let totals: Vec<(usize, usize)> = level_votes
+            .iter()
+            .enumerate()
+            .map(|(candidate, votes)| (candidate, votes.iter().take(level + 1).sum()))
+            .sorted_by(|a: &(usize, usize), b| b.1.cmp(&a.1))
+            .collect();
+
+        let reached = totals[0].1 >= majority;
+        levels.push(totals);
+
+        if reached {
+            majority_level = Some(level);
+            break;
+        }
+    }
+
+    let winner = levels.last().and_then(|totals| match totals.as_slice() {
+        [first, second, ..] if first.1 == second.1 => None,
+        [first, ..] if first.1 > 0 => Some(first.0),
+        _ => None,
+    });
+
+    BucklinResult {
+        levels,
+        majority,
+        majority_level,
+        winner,
+    }
+}
+
+impl CountingMethod for Bucklin {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let level_votes: Vec<Vec<usize>> = voting
+            .candidates
+            .iter()
+            .map(|candidate| candidate.votes.clone())
+            .collect();
+
+        let result = count(&level_votes, voting.counted_papers().count());
+
+        let rounds: Vec<Round> = result
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(level, totals)| Round {
+                title: format!("Level {}", level + 1),
+                tallies: totals
+                    .iter()
+                    .map(|(candidate, votes)| (*candidate, *votes as f64))
+                    .collect(),
+                elected: if result.levels.len() == level + 1 {
+                    result.winner.into_iter().collect()
+                } else {
+                    vec![]
+                },
+                notes: vec![if result.majority_level == Some(level) {
+                    format!("majority of {} reached", result.majority)
+                } else {
+                    format!("no majority of {}", result.majority)
+                }],
+                ..Round::default()
+            })
+            .collect();
+
+        let ranking = rounds
+            .last()
+            .map(|round| elimination_ranking(&round.tallies, &[]))
+            .unwrap_or_default();
+
+        CountResult {
+            rounds,
+            ranking,
+            elected: result.winner.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::bucklin::count;
+
+    #[test]
+    fn first_level_majority() {
+        let result = count(&[vec![3, 0], vec![1, 2], vec![0, 2]], 4);
+
+        assert_eq!(result.majority, 3);
+        assert_eq!(result.majority_level, Some(0));
+        assert_eq!(result.winner, Some(0));
+        assert_eq!(result.levels.len(), 1);
+    }
+
+    #[test]
+    fn adds_later_levels() {
+        let result = count(&[vec![2, 0, 0], vec![2, 3, 0], vec![1, 1, 0]], 5);
+
+        assert_eq!(result.majority_level, Some(1));
+        assert_eq!(result.levels[1][0], (1, 5));
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn no_majority_takes_highest_total() {
+        let result = count(&[vec![2, 0], vec![1, 0], vec![1, 0]], 5);
+
+        assert_eq!(result.majority_level, None);
+        assert_eq!(result.levels.len(), 2);
+        assert_eq!(result.winner, Some(0));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::voting::counting::{CountResult, Method};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// the papers of one election counted by every method that reads them
+pub struct Comparison {
+    pub results: Vec<(Method, CountResult)>,
+    /// the winners most methods agree on
+    pub winners: Vec<usize>,
+    /// the ranking most methods agree on
+    pub ranking: Vec<Vec<usize>>,
+}
+
+impl Comparison {
+    pub fn new(voting: &Voting) -> Comparison {
+        let results: Vec<(Method, CountResult)> = Method::reading(voting.ballot_kind)
+            .into_iter()
+            .map(|method| {
+                let result = method.counter().count(voting, &mut TieBreaker::new(voting));
+                (method, result)
+            })
+            .collect();
+
+        let winners = most_common(results.iter().map(|(_, result)| winners(result)));
+        let ranking = most_common(results.iter().map(|(_, result)| ranking(result)));
+
+        Comparison {
+            results,
+            winners,
+            ranking,
+        }
+    }
+
+    /// the methods electing someone else than most methods
+    pub fn disagreeing(&self) -> Vec<Method> {
+        self.results
+            .iter()
+            .filter(|(_, result)| winners(result) != self.winners)
+            .map(|(method, _)| *method)
+            .collect()
+    }
+}
+
+/// the elected candidates regardless of the order they were elected in
+pub fn winners(result: &CountResult) -> Vec<usize> {
+    result.elected.iter().copied().sorted().collect()
+}
+
+/// the ranking with every tied group in candidate order
+pub fn ranking(result: &CountResult) -> Vec<Vec<usize>> {
+    result
+        .ranking
+        .iter()
+        .map(|group| group.iter().copied().sorted().collect())
+        .collect()
+}
+
+/// the value occurring most often - the earliest one on equal counts
+fn most_common<T: PartialEq + Default>(values: impl Iterator<Item = T>) -> T {
+    let mut counted: Vec<(T, usize)> = vec![];
+
+    for value in values {
+        match counted.iter_mut().find(|(other, _)| *other == value) {
+            Some((_, count)) => *count += 1,
+            None => counted.push((value, 1)),
+        }
+    }
+
+    counted
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(value, _)| value)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::comparison::Comparison;
+    use crate::voting::counting::Method;
+    use crate::voting::Voting;
+
+    fn vote(voting: &mut Voting, names: &[&str]) {
+        for (selection, name) in voting.candidate_selections.iter_mut().zip(names) {
+            selection.search_text = name.to_string();
+        }
+
+        voting.vote();
+    }
+
+    #[test]
+    fn only_methods_reading_the_papers() {
+        let candidates = vec![
+            Candidate::new("huff".to_string(), 1),
+            Candidate::new("puff".to_string(), 1),
+        ];
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+        voting.ballot_kind = BallotKind::Approval;
+
+        let comparison = Comparison::new(&voting);
+        let methods: Vec<Method> = comparison
+            .results
+            .iter()
+            .map(|(method, _)| *method)
+            .collect();
+
+        assert!(methods.contains(&Method::Approval));
+        assert!(!methods.contains(&Method::Irv));
+    }
+
+    #[test]
+    fn finds_disagreeing_methods() {
+        let candidates = vec![
+            Candidate::new("huff".to_string(), 3),
+            Candidate::new("puff".to_string(), 3),
+            Candidate::new("muff".to_string(), 3),
+        ];
+        let mut voting = Voting::new(candidates, "test.txt", 3).unwrap();
+
+        // huff has the most first votes, puff is everybody's compromise
+        for _ in 0..4 {
+            vote(&mut voting, &["huff", "puff", "muff"]);
+        }
+        for _ in 0..3 {
+            vote(&mut voting, &["muff", "puff", "huff"]);
+        }
+        for _ in 0..2 {
+            vote(&mut voting, &["puff", "muff", "huff"]);
+        }
+
+        let comparison = Comparison::new(&voting);
+
+        assert_eq!(comparison.winners, vec![1]);
+        assert!(comparison.disagreeing().contains(&Method::DHondt));
+        assert!(!comparison.disagreeing().contains(&Method::Schulze));
+    }
+}
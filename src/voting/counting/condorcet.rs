@@ -0,0 +1,112 @@
+/// head-to-head results of every pair of candidates
+#[derive(Debug, PartialEq)]
+pub struct PairwiseMatrix {
+    /// `preferences[a][b]` is the number of ballots ranking a above b
+    pub preferences: Vec<Vec<usize>>,
+}
+
+impl PairwiseMatrix {
+    /// a ranked candidate is preferred to every candidate the ballot leaves out
+    pub fn new(candidate_count: usize, rankings: &[Vec<usize>]) -> PairwiseMatrix {
+        let mut preferences = vec![vec![0; candidate_count]; candidate_count];
+
+        for ranking in rankings {
+            for (position, winner) in ranking.iter().enumerate() {
+                for (loser, count) in preferences[*winner].iter_mut().enumerate() {
+                    if loser != *winner && !ranking[..position].contains(&loser) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        PairwiseMatrix { preferences }
+    }
+
+    pub fn size(&self) -> usize {
+        self.preferences.len()
+    }
+
+    pub fn beats(&self, a: usize, b: usize) -> bool {
+        self.preferences[a][b] > self.preferences[b][a]
+    }
+
+    /// the candidate beating every other candidate head-to-head
+    pub fn condorcet_winner(&self) -> Option<usize> {
+        (0..self.size()).find(|a| (0..self.size()).all(|b| *a == b || self.beats(*a, b)))
+    }
+
+    /// the candidate losing against every other candidate head-to-head
+    pub fn condorcet_loser(&self) -> Option<usize> {
+        (0..self.size()).find(|a| (0..self.size()).all(|b| *a == b || self.beats(b, *a)))
+    }
+}
+
+/// one line per candidate - column `n` is the candidate in line `n`
+pub fn matrix_lines(names: &[String], matrix: &[Vec<usize>]) -> Vec<String> {
+    let header = (1..=matrix.len()).map(|column| format!("{:>4}", column));
+    let mut lines = vec![format!("   |{}", header.collect::<String>())];
+
+    for (row, values) in matrix.iter().enumerate() {
+        let values = values.iter().enumerate().map(|(column, value)| {
+            if row == column {
+                format!("{:>4}", "-")
+            } else {
+                format!("{:>4}", value)
+            }
+        });
+
+        lines.push(format!(
+            "{:>2} |{} {}",
+            row + 1,
+            values.collect::<String>(),
+            names[row]
+        ));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::condorcet::{matrix_lines, PairwiseMatrix};
+
+    #[test]
+    fn counts_unranked_as_lower() {
+        let matrix = PairwiseMatrix::new(3, &[vec![0, 1], vec![2]]);
+
+        assert_eq!(
+            matrix.preferences,
+            vec![vec![0, 1, 1], vec![0, 0, 1], vec![1, 1, 0]]
+        );
+    }
+
+    #[test]
+    fn winner_and_loser() {
+        let matrix = PairwiseMatrix::new(3, &[vec![0, 1, 2], vec![1, 0, 2], vec![0, 2, 1]]);
+
+        assert_eq!(matrix.condorcet_winner(), Some(0));
+        assert_eq!(matrix.condorcet_loser(), Some(2));
+    }
+
+    #[test]
+    fn cycle_has_no_winner() {
+        let matrix = PairwiseMatrix::new(3, &[vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]]);
+
+        assert_eq!(matrix.condorcet_winner(), None);
+        assert_eq!(matrix.condorcet_loser(), None);
+    }
+
+    #[test]
+    fn matrix_lines_returns_correct() {
+        let lines = matrix_lines(
+            &["huff".to_string(), "puff".to_string()],
+            &[vec![0, 2], vec![1, 0]],
+        );
+
+        assert_eq!(
+            lines,
+            vec!["   |   1   2", " 1 |   -   2 huff", " 2 |   1   - puff"]
+        );
+    }
+}
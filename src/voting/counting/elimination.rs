@@ -0,0 +1,367 @@
+use crate::voting::counting::{elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::{last_place, TieBreaker, Ties};
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// how the candidates to eliminate are picked each round
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EliminationRule {
+    /// the lowest borda score
+    Baldwin,
+    /// everyone below the average borda score
+    Nanson,
+    /// the candidate ranked last most often
+    Coombs,
+}
+
+pub struct EliminationRound {
+    /// (candidate index, borda score or last places) of every remaining candidate, highest first
+    pub tallies: Vec<(usize, f64)>,
+    pub eliminated: Vec<usize>,
+    /// how a tie for the elimination was decided
+    pub tie_break: Option<String>,
+}
+
+pub struct EliminationResult {
+    pub rounds: Vec<EliminationRound>,
+    pub winner: Option<usize>,
+}
+
+/// counts the rankings (candidate indices, best first) by repeatedly eliminating
+/// candidates - every round is recomputed from the rankings restricted to the remaining candidates
+///
+/// ties for the elimination are decided by `ties` - the count stops while they are still tied
+pub fn count(
+    rule: EliminationRule,
+    candidate_count: usize,
+    rankings: &[Vec<usize>],
+    ties: &mut Ties,
+) -> EliminationResult {
+    let mut remaining: Vec<usize> = (0..candidate_count).collect();
+    let mut rounds = vec![];
+
+    loop {
+        let restricted: Vec<Vec<usize>> = rankings
+            .iter()
+            .map(|ranking| {
+                ranking
+                    .iter()
+                    .filter(|candidate| remaining.contains(candidate))
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        let values = match rule {
+            EliminationRule::Baldwin | EliminationRule::Nanson => {
+                borda(candidate_count, remaining.len(), &restricted)
+            }
+            EliminationRule::Coombs => last_places(candidate_count, &remaining, &restricted),
+        };
+
+        let tallies: Vec<(usize, f64)> = remaining
+            .iter()
+            .map(|candidate| (*candidate, values[*candidate]))
+            .sorted_by(|a, b| b.1.total_cmp(&a.1))
+            .collect();
+
+        let winner = match rule {
+            _ if remaining.len() == 1 => Some(remaining[0]),
+            EliminationRule::Coombs => first_majority(candidate_count, &restricted),
+            _ => None,
+        };
+
+        if winner.is_some() || rankings.is_empty() {
+            rounds.push(EliminationRound {
+                tallies,
+                eliminated: vec![],
+                tie_break: None,
+            });
+
+            return EliminationResult { rounds, winner };
+        }
+
+        // the tie break prefers greater values, coombs eliminates the most last places
+        let previous: Vec<Vec<(usize, f64)>> = rounds
+            .iter()
+            .map(|round| &round.tallies)
+            .chain([&tallies])
+            .map(|tallies| match rule {
+                EliminationRule::Coombs => tallies
+                    .iter()
+                    .map(|(candidate, last)| (*candidate, -last))
+                    .collect(),
+                _ => tallies.clone(),
+            })
+            .collect();
+
+        let (eliminated, tie_break) = match rule {
+            EliminationRule::Baldwin => {
+                let lowest = tallies.last().unwrap().1;
+                eliminate_one(&tallies, lowest, &previous, ties)
+            }
+            EliminationRule::Nanson => {
+                let average =
+                    tallies.iter().map(|(_, score)| score).sum::<f64>() / tallies.len() as f64;
+
+                let below = tallies
+                    .iter()
+                    .filter(|(_, score)| *score < average)
+                    .map(|(candidate, _)| *candidate)
+                    .collect();
+
+                (below, None)
+            }
+            EliminationRule::Coombs => {
+                let most = tallies.first().unwrap().1;
+                eliminate_one(&tallies, most, &previous, ties)
+            }
+        };
+
+        // the remaining candidates are still tied
+        if eliminated.is_empty() {
+            rounds.push(EliminationRound {
+                tallies,
+                eliminated,
+                tie_break,
+            });
+
+            return EliminationResult {
+                rounds,
+                winner: None,
+            };
+        }
+
+        remaining.retain(|candidate| !eliminated.contains(candidate));
+
+        rounds.push(EliminationRound {
+            tallies,
+            eliminated,
+            tie_break,
+        });
+    }
+}
+
+/// the candidate holding `value` - `ties` decides when several do and nobody goes
+/// while they are still tied
+fn eliminate_one(
+    tallies: &[(usize, f64)],
+    value: f64,
+    previous: &[Vec<(usize, f64)>],
+    ties: &mut Ties,
+) -> (Vec<usize>, Option<String>) {
+    let tied: Vec<usize> = tallies
+        .iter()
+        .filter(|(_, other)| *other == value)
+        .map(|(candidate, _)| *candidate)
+        .collect();
+
+    if tied.len() == 1 {
+        return (tied, None);
+    }
+
+    let (order, note) = ties(&tied, previous);
+    (last_place(&order).into_iter().collect(), Some(note))
+}
+
+/// `remaining - 1 - position` points per ranked candidate, unranked candidates get nothing
+fn borda(candidate_count: usize, remaining: usize, rankings: &[Vec<usize>]) -> Vec<f64> {
+    let mut scores = vec![0.0; candidate_count];
+
+    for ranking in rankings {
+        for (position, candidate) in ranking.iter().enumerate() {
+            scores[*candidate] += (remaining - 1 - position) as f64;
+        }
+    }
+
+    scores
+}
+
+/// how often every candidate is ranked last - candidates left off a paper share its last place
+fn last_places(candidate_count: usize, remaining: &[usize], rankings: &[Vec<usize>]) -> Vec<f64> {
+    let mut last = vec![0.0; candidate_count];
+
+    for ranking in rankings {
+        let unranked: Vec<&usize> = remaining
+            .iter()
+            .filter(|candidate| !ranking.contains(candidate))
+            .collect();
+
+        match (unranked.is_empty(), ranking.last()) {
+            (true, Some(candidate)) => last[*candidate] += 1.0,
+            (true, None) => {}
+            (false, _) => {
+                for candidate in &unranked {
+                    last[**candidate] += 1.0 / unranked.len() as f64;
+                }
+            }
+        }
+    }
+
+    last
+}
+
+fn first_majority(candidate_count: usize, rankings: &[Vec<usize>]) -> Option<usize> {
+    let mut first = vec![0; candidate_count];
+
+    for ranking in rankings {
+        if let Some(candidate) = ranking.first() {
+            first[*candidate] += 1;
+        }
+    }
+
+    let active: usize = first.iter().sum();
+
+    (0..candidate_count).find(|candidate| first[*candidate] * 2 > active)
+}
+
+pub struct Elimination(pub EliminationRule);
+
+impl CountingMethod for Elimination {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult {
+        let result = count(
+            self.0,
+            voting.candidates.len(),
+            &voting.rankings(),
+            &mut |group, previous| ties.order(group, previous),
+        );
+
+        let label = match self.0 {
+            EliminationRule::Baldwin | EliminationRule::Nanson => "borda score",
+            EliminationRule::Coombs => "last places",
+        };
+
+        let rounds: Vec<Round> = result
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(index, round)| Round {
+                title: format!("Round {} - {}", index + 1, label),
+                tallies: round.tallies.clone(),
+                elected: if round.eliminated.is_empty() {
+                    result.winner.into_iter().collect()
+                } else {
+                    vec![]
+                },
+                eliminated: round.eliminated.clone(),
+                notes: round.tie_break.clone().into_iter().collect(),
+            })
+            .collect();
+
+        let eliminated: Vec<Vec<usize>> = rounds
+            .iter()
+            .map(|round| round.eliminated.clone())
+            .collect();
+
+        // coombs tallies last places, so only the winner stands out of its last round
+        let last_round: Vec<(usize, f64)> = match (self.0, result.winner) {
+            (EliminationRule::Coombs, Some(winner)) => rounds
+                .last()
+                .unwrap()
+                .tallies
+                .iter()
+                .map(|(candidate, _)| (*candidate, (*candidate == winner) as usize as f64))
+                .collect(),
+            _ => rounds.last().unwrap().tallies.clone(),
+        };
+
+        CountResult {
+            ranking: elimination_ranking(&last_round, &eliminated),
+            elected: result.winner.into_iter().collect(),
+            rounds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::elimination::{count, EliminationRule};
+
+    fn tied(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        (vec![group.to_vec()], "still tied".to_string())
+    }
+
+    fn rankings() -> Vec<Vec<usize>> {
+        let mut rankings = vec![];
+        rankings.extend(vec![vec![0, 1, 2]; 4]);
+        rankings.extend(vec![vec![1, 2, 0]; 3]);
+        rankings.extend(vec![vec![2, 1, 0]; 2]);
+        rankings
+    }
+
+    #[test]
+    fn baldwin_eliminates_lowest_borda() {
+        // borda: 0 = 8, 1 = 12, 2 = 7
+        let result = count(EliminationRule::Baldwin, 3, &rankings(), &mut tied);
+
+        assert_eq!(
+            result.rounds[0].tallies,
+            vec![(1, 12.0), (0, 8.0), (2, 7.0)]
+        );
+        assert_eq!(result.rounds[0].eliminated, vec![2]);
+        assert_eq!(result.rounds[1].eliminated, vec![0]);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn nanson_eliminates_below_average() {
+        // average 9 - 0 and 2 are below
+        let result = count(EliminationRule::Nanson, 3, &rankings(), &mut tied);
+
+        assert_eq!(result.rounds[0].eliminated, vec![0, 2]);
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn coombs_eliminates_most_last_places() {
+        // last places: 0 = 5, 2 = 4
+        let result = count(EliminationRule::Coombs, 3, &rankings(), &mut tied);
+
+        assert_eq!(result.rounds[0].tallies[0], (0, 5.0));
+        assert_eq!(result.rounds[0].eliminated, vec![0]);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn tie_has_no_winner() {
+        let rankings = [vec![0, 1], vec![1, 0]];
+
+        let result = count(EliminationRule::Nanson, 2, &rankings, &mut tied);
+
+        assert_eq!(result.winner, None);
+        assert!(result.rounds[0].eliminated.is_empty());
+    }
+
+    #[test]
+    fn tied_eliminations_go_to_the_tie_break() {
+        let rankings = [vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]];
+
+        for rule in [EliminationRule::Baldwin, EliminationRule::Coombs] {
+            let result = count(rule, 3, &rankings, &mut tied);
+
+            assert_eq!(result.winner, None);
+            assert!(result.rounds[0].eliminated.is_empty());
+            assert_eq!(result.rounds[0].tie_break, Some("still tied".to_string()));
+        }
+
+        // the tie break puts candidate 1 last - coombs sees the negated last places
+        let result = count(
+            EliminationRule::Coombs,
+            3,
+            &rankings,
+            &mut |group, previous| {
+                assert_eq!(previous[0][0].1, -1.0);
+                let order = [0, 2, 1]
+                    .into_iter()
+                    .filter(|candidate| group.contains(candidate))
+                    .map(|candidate| vec![candidate])
+                    .collect();
+                (order, "decided".to_string())
+            },
+        );
+
+        assert_eq!(result.rounds[0].eliminated, vec![1]);
+        assert_eq!(result.rounds[0].tie_break, Some("decided".to_string()));
+    }
+}
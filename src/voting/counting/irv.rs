@@ -0,0 +1,254 @@
+use crate::voting::counting::{
+    eliminate_lowest, elimination_ranking, CountResult, CountingMethod, Round,
+};
+use crate::voting::tie_break::{TieBreaker, Ties};
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+pub struct InstantRunoff;
+
+pub struct IrvRound {
+    /// (candidate index, votes) of every remaining candidate, most votes first
+    pub tallies: Vec<(usize, usize)>,
+    pub exhausted: usize,
+    pub eliminated: Vec<usize>,
+    /// how a tie for the last place was decided
+    pub tie_break: Option<String>,
+}
+
+pub struct IrvResult {
+    pub rounds: Vec<IrvRound>,
+    pub winner: Option<usize>,
+}
+
+/// counts the rankings (candidate indices, best first) by instant runoff
+///
+/// ties for the last place are decided by `ties` - the count stops while they are still tied
+pub fn count(candidate_count: usize, rankings: &[Vec<usize>], ties: &mut Ties) -> IrvResult {
+    let mut remaining: Vec<usize> = (0..candidate_count).collect();
+    let mut rounds = vec![];
+
+    loop {
+        let mut votes = vec![0; candidate_count];
+        let mut exhausted = 0;
+
+        for ranking in rankings {
+            match ranking.iter().find(|index| remaining.contains(index)) {
+                Some(index) => votes[*index] += 1,
+                None => exhausted += 1,
+            }
+        }
+
+        let tallies: Vec<(usize, usize)> = remaining
+            .iter()
+            .map(|index| (*index, votes[*index]))
+            .sorted_by(|a, b| b.1.cmp(&a.1))
+            .collect();
+
+        let active = rankings.len() - exhausted;
+
+        let winner = match tallies.first() {
+            Some((index, votes)) if tallies.len() == 1 || (active > 0 && votes * 2 > active) => {
+                Some(*index)
+            }
+            _ => None,
+        };
+
+        if winner.is_some() || active == 0 {
+            rounds.push(IrvRound {
+                tallies,
+                exhausted,
+                eliminated: vec![],
+                tie_break: None,
+            });
+
+            return IrvResult { rounds, winner };
+        }
+
+        let previous: Vec<Vec<(usize, f64)>> = rounds
+            .iter()
+            .map(|round| &round.tallies)
+            .chain([&tallies])
+            .map(|tallies| {
+                tallies
+                    .iter()
+                    .map(|(index, votes)| (*index, *votes as f64))
+                    .collect()
+            })
+            .collect();
+
+        let (eliminated, tie_break) = eliminate_lowest(previous.last().unwrap(), &previous, ties);
+        remaining.retain(|index| !eliminated.contains(index));
+
+        let stuck = eliminated.is_empty();
+
+        rounds.push(IrvRound {
+            tallies,
+            exhausted,
+            eliminated,
+            tie_break,
+        });
+
+        if stuck {
+            return IrvResult {
+                rounds,
+                winner: None,
+            };
+        }
+    }
+}
+
+impl CountingMethod for InstantRunoff {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult {
+        let result = count(
+            voting.candidates.len(),
+            &voting.rankings(),
+            &mut |group, previous| ties.order(group, previous),
+        );
+
+        let rounds: Vec<Round> = result
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(index, round)| Round {
+                title: format!("Round {}", index + 1),
+                tallies: round
+                    .tallies
+                    .iter()
+                    .map(|(candidate, votes)| (*candidate, *votes as f64))
+                    .collect(),
+                elected: if round.eliminated.is_empty() {
+                    result.winner.into_iter().collect()
+                } else {
+                    vec![]
+                },
+                eliminated: round.eliminated.clone(),
+                notes: [format!("{} exhausted", round.exhausted)]
+                    .into_iter()
+                    .chain(round.tie_break.clone())
+                    .collect(),
+            })
+            .collect();
+
+        let eliminated: Vec<Vec<usize>> = rounds
+            .iter()
+            .map(|round| round.eliminated.clone())
+            .collect();
+
+        CountResult {
+            ranking: elimination_ranking(&rounds.last().unwrap().tallies, &eliminated),
+            elected: result.winner.into_iter().collect(),
+            rounds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::irv::{count, InstantRunoff};
+    use crate::voting::counting::CountingMethod;
+    use crate::voting::tie_break::{TieBreak, TieBreaker};
+    use crate::voting::Voting;
+
+    /// puts the tied candidates in the order they are listed
+    fn listed(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        let order = group.iter().map(|candidate| vec![*candidate]).collect();
+        (order, "listed".to_string())
+    }
+
+    fn tied(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        (vec![group.to_vec()], "still tied".to_string())
+    }
+
+    #[test]
+    fn first_round_majority() {
+        let result = count(3, &[vec![0, 1], vec![0, 2], vec![1, 0]], &mut tied);
+
+        assert_eq!(result.winner, Some(0));
+        assert_eq!(result.rounds.len(), 1);
+        assert_eq!(result.rounds[0].tallies, vec![(0, 2), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn transfers_eliminated_votes() {
+        let rankings = [vec![0], vec![0], vec![1], vec![1], vec![2, 1]];
+
+        let result = count(3, &rankings, &mut tied);
+
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.rounds[0].eliminated, vec![2]);
+        assert_eq!(result.rounds[1].tallies, vec![(1, 3), (0, 2)]);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn tracks_exhausted_ballots() {
+        let rankings = [vec![0], vec![0], vec![1], vec![2], vec![2, 1]];
+
+        let result = count(3, &rankings, &mut listed);
+
+        assert_eq!(result.rounds[0].eliminated, vec![1]);
+        assert_eq!(result.rounds[1].exhausted, 1);
+        assert_eq!(result.rounds[1].tie_break, Some("listed".to_string()));
+        assert_eq!(result.winner, Some(0));
+    }
+
+    #[test]
+    fn no_ballots() {
+        let result = count(2, &[], &mut tied);
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.rounds.len(), 1);
+    }
+
+    #[test]
+    fn hopeless_candidates_go_together() {
+        let rankings = [
+            vec![0],
+            vec![0],
+            vec![0],
+            vec![1],
+            vec![1],
+            vec![1],
+            vec![2],
+            vec![3],
+        ];
+
+        let result = count(4, &rankings, &mut tied);
+
+        assert_eq!(result.rounds[0].eliminated, vec![2, 3]);
+        assert_eq!(result.rounds[0].tie_break, None);
+    }
+
+    #[test]
+    fn tied_elimination_goes_to_the_tie_break() {
+        let candidates = ["a", "b", "c"]
+            .iter()
+            .map(|name| Candidate::new(name.to_string(), 1))
+            .collect();
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+
+        for name in ["a", "b", "c"] {
+            voting.candidate_selections[0].search_text = name.to_string();
+            voting.vote();
+        }
+
+        let result = InstantRunoff.count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.rounds.len(), 1);
+        assert!(result.elected.is_empty());
+        assert_eq!(result.ranking, vec![vec![0, 1, 2]]);
+        assert!(result.rounds[0]
+            .notes
+            .contains(&"a, b, c tied - none: still tied".to_string()));
+
+        voting.tie_break = TieBreak::Lots(7);
+
+        let result = InstantRunoff.count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.rounds.len(), 3);
+        assert_eq!(result.elected.len(), 1);
+        assert!(result.rounds[0].notes[1].starts_with("a, b, c tied - lots:7: "));
+    }
+}
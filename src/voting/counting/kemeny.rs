@@ -0,0 +1,227 @@
+use crate::voting::counting::condorcet::PairwiseMatrix;
+use crate::voting::counting::{elect_top, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// fields up to this size are solved by trying every order
+pub const EXACT_LIMIT: usize = 8;
+
+/// tied orders listed in the result
+const SHOWN_TIES: usize = 10;
+
+/// the full order agreeing with the most pairwise preferences
+pub struct KemenyYoung;
+
+pub struct KemenyResult {
+    /// every order reaching the best agreement score, best candidate first
+    pub orders: Vec<Vec<usize>>,
+    /// how many pairwise preferences the orders agree with
+    pub score: usize,
+    /// false when the field was too large and a local search was used
+    pub exact: bool,
+}
+
+/// how many pairwise preferences agree with the order
+pub fn agreement(matrix: &PairwiseMatrix, order: &[usize]) -> usize {
+    order
+        .iter()
+        .enumerate()
+        .flat_map(|(position, above)| {
+            order[position + 1..]
+                .iter()
+                .map(move |below| matrix.preferences[*above][*below])
+        })
+        .sum()
+}
+
+pub fn count(matrix: &PairwiseMatrix) -> KemenyResult {
+    if matrix.size() > EXACT_LIMIT {
+        let order = local_search(matrix);
+
+        return KemenyResult {
+            score: agreement(matrix, &order),
+            orders: vec![order],
+            exact: false,
+        };
+    }
+
+    let mut orders = vec![];
+    let mut score = 0;
+
+    for order in (0..matrix.size()).permutations(matrix.size()) {
+        let order_score = agreement(matrix, &order);
+
+        if order_score > score {
+            score = order_score;
+            orders.clear();
+        }
+
+        if order_score == score {
+            orders.push(order);
+        }
+    }
+
+    KemenyResult {
+        orders,
+        score,
+        exact: true,
+    }
+}
+
+/// starts from the order of pairwise margins and moves single candidates
+/// while that raises the agreement score
+fn local_search(matrix: &PairwiseMatrix) -> Vec<usize> {
+    let size = matrix.size();
+    let margin = |candidate: usize| -> i64 {
+        (0..size)
+            .map(|other| {
+                matrix.preferences[candidate][other] as i64
+                    - matrix.preferences[other][candidate] as i64
+            })
+            .sum()
+    };
+
+    let mut order: Vec<usize> = (0..size)
+        .sorted_by_key(|candidate| -margin(*candidate))
+        .collect();
+    let mut score = agreement(matrix, &order);
+
+    'improve: loop {
+        for from in 0..size {
+            for to in 0..size {
+                if from == to {
+                    continue;
+                }
+
+                let mut moved = order.clone();
+                let candidate = moved.remove(from);
+                moved.insert(to, candidate);
+
+                let moved_score = agreement(matrix, &moved);
+                if moved_score > score {
+                    order = moved;
+                    score = moved_score;
+                    continue 'improve;
+                }
+            }
+        }
+
+        return order;
+    }
+}
+
+impl CountingMethod for KemenyYoung {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let matrix = PairwiseMatrix::new(voting.candidates.len(), &voting.rankings());
+        let result = count(&matrix);
+
+        let names = voting.candidate_names();
+        let describe = |order: &Vec<usize>| {
+            order
+                .iter()
+                .map(|candidate| names[*candidate].as_str())
+                .join(" > ")
+        };
+
+        let mut notes = vec![if result.exact {
+            "exact - every order was checked".to_string()
+        } else {
+            format!(
+                "heuristic - more than {} candidates, the order may not be optimal",
+                EXACT_LIMIT
+            )
+        }];
+
+        notes.push(format!("agreement score {}", result.score));
+
+        if result.orders.len() > 1 {
+            notes.push(format!("{} orders tied", result.orders.len()));
+        }
+
+        notes.extend(result.orders.iter().take(SHOWN_TIES).map(describe));
+
+        if result.orders.len() > SHOWN_TIES {
+            notes.push(format!("... {} more", result.orders.len() - SHOWN_TIES));
+        }
+
+        // candidates share a place unless every tied order puts them in the same position
+        let mut ranking: Vec<Vec<usize>> = vec![];
+        let first = &result.orders[0];
+        let mut position = 0;
+
+        while position < first.len() {
+            let mut end = position + 1;
+
+            while end < first.len()
+                && result.orders.iter().any(|order| {
+                    order[position..end]
+                        .iter()
+                        .sorted()
+                        .ne(first[position..end].iter().sorted())
+                })
+            {
+                end += 1;
+            }
+
+            ranking.push(first[position..end].to_vec());
+            position = end;
+        }
+
+        CountResult {
+            rounds: vec![Round {
+                title: "Kemeny-Young".to_string(),
+                notes,
+                ..Round::default()
+            }],
+            elected: elect_top(&ranking, voting.seats),
+            ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::condorcet::PairwiseMatrix;
+    use crate::voting::counting::kemeny::{agreement, count};
+
+    #[test]
+    fn agreement_score() {
+        let matrix = PairwiseMatrix::new(3, &[vec![0, 1, 2], vec![1, 0, 2]]);
+
+        assert_eq!(agreement(&matrix, &[0, 1, 2]), 1 + 2 + 2);
+        assert_eq!(agreement(&matrix, &[2, 1, 0]), 1);
+    }
+
+    #[test]
+    fn finds_best_order() {
+        let mut rankings = vec![];
+        rankings.extend(vec![vec![0, 1, 2]; 4]);
+        rankings.extend(vec![vec![1, 2, 0]; 3]);
+        rankings.extend(vec![vec![2, 0, 1]; 2]);
+
+        let result = count(&PairwiseMatrix::new(3, &rankings));
+
+        assert!(result.exact);
+        assert_eq!(result.orders, vec![vec![0, 1, 2]]);
+        assert_eq!(result.score, 6 + 4 + 7);
+    }
+
+    #[test]
+    fn reports_tied_orders() {
+        let result = count(&PairwiseMatrix::new(2, &[vec![0, 1], vec![1, 0]]));
+
+        assert_eq!(result.orders.len(), 2);
+        assert_eq!(result.score, 1);
+    }
+
+    #[test]
+    fn large_fields_use_heuristic() {
+        let rankings = vec![(0..10).rev().collect::<Vec<usize>>()];
+
+        let result = count(&PairwiseMatrix::new(10, &rankings));
+
+        assert!(!result.exact);
+        assert_eq!(result.orders, rankings);
+    }
+}
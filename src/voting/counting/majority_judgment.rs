@@ -0,0 +1,178 @@
+use crate::voting::ballot::Grade;
+use crate::voting::counting::{elect_top, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// ranks by the median grade - ties are broken by repeatedly
+/// removing one median grade of the tied candidates and comparing again
+pub struct MajorityJudgment;
+
+/// the lower median of grades sorted best first
+pub fn median(grades: &[Grade]) -> Option<Grade> {
+    grades.get(grades.len() / 2).copied()
+}
+
+/// the sequence of medians taken while removing one median at a time -
+/// comparing these sequences is the standard majority judgment tie-break
+pub fn majority_value(grades: &[Grade]) -> Vec<Grade> {
+    let mut grades: Vec<Grade> = grades.iter().copied().sorted().collect();
+    let mut value = vec![];
+
+    while let Some(grade) = median(&grades) {
+        value.push(grade);
+        grades.remove(grades.len() / 2);
+    }
+
+    value
+}
+
+/// how many papers gave each grade, best grade first
+pub fn distribution(grades: &[Grade]) -> Vec<(Grade, usize)> {
+    Grade::ALL
+        .iter()
+        .map(|grade| {
+            (
+                *grade,
+                grades.iter().filter(|other| *other == grade).count(),
+            )
+        })
+        .collect()
+}
+
+/// groups of tied candidates, best majority value first
+pub fn count(candidate_count: usize, ballots: &[Vec<Grade>]) -> Vec<Vec<usize>> {
+    let values: Vec<Vec<Grade>> = (0..candidate_count)
+        .map(|candidate| {
+            let grades: Vec<Grade> = ballots.iter().map(|ballot| ballot[candidate]).collect();
+            majority_value(&grades)
+        })
+        .collect();
+
+    (0..candidate_count)
+        .sorted_by(|a, b| values[*a].cmp(&values[*b]))
+        .group_by(|candidate| &values[*candidate])
+        .into_iter()
+        .map(|(_, group)| group.collect())
+        .collect()
+}
+
+impl CountingMethod for MajorityJudgment {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let ballots = voting.grade_ballots();
+        let ranking = count(voting.candidates.len(), &ballots);
+        let elected = elect_top(&ranking, voting.seats);
+
+        let mut tallies = vec![];
+        let mut notes = vec![];
+
+        for candidate in ranking.iter().flatten() {
+            let grades: Vec<Grade> = ballots
+                .iter()
+                .map(|ballot| ballot[*candidate])
+                .sorted()
+                .collect();
+
+            let median = median(&grades);
+            let at_median = grades
+                .iter()
+                .filter(|grade| Some(**grade) <= median)
+                .count();
+
+            tallies.push((*candidate, at_median as f64));
+            notes.push(format!(
+                "{} - median {} - {}",
+                voting.candidates[*candidate].name,
+                median.map_or("none".to_string(), |grade| grade.to_string()),
+                distribution(&grades)
+                    .iter()
+                    .map(|(grade, count)| format!("{} {}", grade, count))
+                    .join(", ")
+            ));
+        }
+
+        notes.push("papers grading at the median or better".to_string());
+
+        let round = Round {
+            title: "Median grades".to_string(),
+            tallies,
+            elected: elected.clone(),
+            notes,
+            ..Round::default()
+        };
+
+        CountResult {
+            rounds: vec![round],
+            ranking,
+            elected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::Grade;
+    use crate::voting::counting::majority_judgment::{count, distribution, majority_value, median};
+
+    #[test]
+    fn lower_median() {
+        assert_eq!(median(&[]), None);
+        assert_eq!(
+            median(&[Grade::Excellent, Grade::Good, Grade::Poor]),
+            Some(Grade::Good)
+        );
+        assert_eq!(
+            median(&[Grade::Excellent, Grade::Good, Grade::Poor, Grade::Reject]),
+            Some(Grade::Poor)
+        );
+    }
+
+    #[test]
+    fn removes_medians_in_turn() {
+        let value = majority_value(&[Grade::Reject, Grade::Excellent, Grade::Good]);
+
+        assert_eq!(value, vec![Grade::Good, Grade::Reject, Grade::Excellent]);
+    }
+
+    #[test]
+    fn best_median_wins() {
+        let ballots = [
+            vec![Grade::Excellent, Grade::Good],
+            vec![Grade::Poor, Grade::Good],
+            vec![Grade::Reject, Grade::VeryGood],
+        ];
+
+        assert_eq!(count(2, &ballots), vec![vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn breaks_equal_medians() {
+        // both have the median good - removing it leaves candidate 0 with the better grades
+        let ballots = [
+            vec![Grade::Excellent, Grade::Good],
+            vec![Grade::Good, Grade::Good],
+            vec![Grade::Poor, Grade::Reject],
+        ];
+
+        assert_eq!(count(2, &ballots), vec![vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn identical_grades_tie() {
+        let ballots = [vec![Grade::Good, Grade::Good, Grade::Reject]];
+
+        assert_eq!(count(3, &ballots), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn counts_distribution() {
+        let distribution = distribution(&[Grade::Good, Grade::Reject, Grade::Good]);
+
+        assert_eq!(distribution[2], (Grade::Good, 2));
+        assert_eq!(distribution[5], (Grade::Reject, 1));
+        assert_eq!(
+            distribution.iter().map(|(_, count)| count).sum::<usize>(),
+            3
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use crate::voting::ballot::BallotKind;
+use crate::voting::counting::approval::Approval;
+use crate::voting::counting::bucklin::Bucklin;
+use crate::voting::counting::elimination::{Elimination, EliminationRule};
+use crate::voting::counting::irv::InstantRunoff;
+use crate::voting::counting::kemeny::KemenyYoung;
+use crate::voting::counting::majority_judgment::MajorityJudgment;
+use crate::voting::counting::party_list::{Apportionment, PartyList};
+use crate::voting::counting::points::Points;
+use crate::voting::counting::positional::Positional;
+use crate::voting::counting::ranked_pairs::RankedPairs;
+use crate::voting::counting::schulze::Schulze;
+use crate::voting::counting::star::Star;
+use crate::voting::counting::stv::SingleTransferableVote;
+use crate::voting::tie_break::{last_place, TieBreaker, Ties};
+use crate::voting::Voting;
+use anyhow::bail;
+use iter_tools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub mod approval;
+pub mod bucklin;
+pub mod comparison;
+pub mod condorcet;
+pub mod elimination;
+pub mod irv;
+pub mod kemeny;
+pub mod majority_judgment;
+pub mod party_list;
+pub mod points;
+pub mod positional;
+pub mod ranked_pairs;
+pub mod schulze;
+pub mod star;
+pub mod stv;
+
+pub trait CountingMethod {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult;
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CountResult {
+    pub rounds: Vec<Round>,
+    /// groups of tied candidates, winners first
+    pub ranking: Vec<Vec<usize>>,
+    /// the candidates filling the seats - empty when a tie decides it
+    pub elected: Vec<usize>,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Round {
+    pub title: String,
+    /// (candidate index, votes or points) in display order
+    pub tallies: Vec<(usize, f64)>,
+    pub elected: Vec<usize>,
+    pub eliminated: Vec<usize>,
+    pub notes: Vec<String>,
+}
+
+/// the counting methods an election can be evaluated with
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Method {
+    #[default]
+    Positional,
+    Irv,
+    Stv,
+    Schulze,
+    RankedPairs,
+    Approval,
+    Star,
+    Bucklin,
+    Baldwin,
+    Nanson,
+    Coombs,
+    KemenyYoung,
+    DHondt,
+    SainteLague,
+    HareNiemeyer,
+    MajorityJudgment,
+    Points,
+}
+
+impl Method {
+    pub const ALL: &'static [Method] = &[
+        Method::Positional,
+        Method::Irv,
+        Method::Stv,
+        Method::Schulze,
+        Method::RankedPairs,
+        Method::Approval,
+        Method::Star,
+        Method::Bucklin,
+        Method::Baldwin,
+        Method::Nanson,
+        Method::Coombs,
+        Method::KemenyYoung,
+        Method::DHondt,
+        Method::SainteLague,
+        Method::HareNiemeyer,
+        Method::MajorityJudgment,
+        Method::Points,
+    ];
+
+    /// the method a new election with this kind of ballot is counted with
+    pub fn default_for(kind: BallotKind) -> Method {
+        match kind {
+            BallotKind::Ranked => Method::Positional,
+            BallotKind::Approval => Method::Approval,
+            BallotKind::Score => Method::Star,
+            BallotKind::Grades => Method::MajorityJudgment,
+            BallotKind::Cumulative | BallotKind::Quadratic => Method::Points,
+        }
+    }
+
+    /// whether the method counts papers of this kind - positional points read every kind
+    /// but grades, which give no points
+    pub fn reads(&self, kind: BallotKind) -> bool {
+        match self {
+            Method::Positional => kind != BallotKind::Grades,
+            Method::Approval => kind == BallotKind::Approval,
+            Method::Star => kind == BallotKind::Score,
+            Method::MajorityJudgment => kind == BallotKind::Grades,
+            Method::Points => matches!(kind, BallotKind::Cumulative | BallotKind::Quadratic),
+            Method::DHondt | Method::SainteLague | Method::HareNiemeyer => {
+                matches!(kind, BallotKind::Ranked | BallotKind::Approval)
+            }
+            _ => kind == BallotKind::Ranked,
+        }
+    }
+
+    /// the methods that count papers of this kind
+    pub fn reading(kind: BallotKind) -> Vec<Method> {
+        Method::ALL
+            .iter()
+            .filter(|method| method.reads(kind))
+            .copied()
+            .collect()
+    }
+
+    pub fn counter(&self) -> Box<dyn CountingMethod> {
+        match self {
+            Method::Positional => Box::new(Positional),
+            Method::Irv => Box::new(InstantRunoff),
+            Method::Stv => Box::new(SingleTransferableVote),
+            Method::Schulze => Box::new(Schulze),
+            Method::RankedPairs => Box::new(RankedPairs),
+            Method::Approval => Box::new(Approval),
+            Method::Star => Box::new(Star),
+            Method::Bucklin => Box::new(Bucklin),
+            Method::Baldwin => Box::new(Elimination(EliminationRule::Baldwin)),
+            Method::Nanson => Box::new(Elimination(EliminationRule::Nanson)),
+            Method::Coombs => Box::new(Elimination(EliminationRule::Coombs)),
+            Method::KemenyYoung => Box::new(KemenyYoung),
+            Method::DHondt => Box::new(PartyList(Apportionment::DHondt)),
+            Method::SainteLague => Box::new(PartyList(Apportionment::SainteLague)),
+            Method::HareNiemeyer => Box::new(PartyList(Apportionment::HareNiemeyer)),
+            Method::MajorityJudgment => Box::new(MajorityJudgment),
+            Method::Points => Box::new(Points),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_lowercase();
+
+        match Method::ALL.iter().find(|method| method.to_string() == text) {
+            Some(method) => Ok(*method),
+            None => bail!(
+                "unknown counting method - {} (use one of {})",
+                text,
+                Method::ALL.iter().join(", ")
+            ),
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Method::Positional => write!(f, "positional"),
+            Method::Irv => write!(f, "irv"),
+            Method::Stv => write!(f, "stv"),
+            Method::Schulze => write!(f, "schulze"),
+            Method::RankedPairs => write!(f, "ranked-pairs"),
+            Method::Approval => write!(f, "approval"),
+            Method::Star => write!(f, "star"),
+            Method::Bucklin => write!(f, "bucklin"),
+            Method::Baldwin => write!(f, "baldwin"),
+            Method::Nanson => write!(f, "nanson"),
+            Method::Coombs => write!(f, "coombs"),
+            Method::KemenyYoung => write!(f, "kemeny-young"),
+            Method::DHondt => write!(f, "dhondt"),
+            Method::SainteLague => write!(f, "sainte-lague"),
+            Method::HareNiemeyer => write!(f, "hare-niemeyer"),
+            Method::MajorityJudgment => write!(f, "majority-judgment"),
+            Method::Points => write!(f, "points"),
+        }
+    }
+}
+
+/// takes whole groups of the ranking as long as they fit into the seats
+pub fn elect_top(ranking: &[Vec<usize>], seats: usize) -> Vec<usize> {
+    let mut elected = vec![];
+
+    for group in ranking {
+        if elected.len() + group.len() > seats {
+            break;
+        }
+
+        elected.extend(group);
+    }
+
+    elected
+}
+
+/// ranks the candidates left in the last round by their tallies,
+/// followed by the groups of candidates eliminated together - last eliminated first
+pub fn elimination_ranking(
+    last_round: &[(usize, f64)],
+    eliminated: &[Vec<usize>],
+) -> Vec<Vec<usize>> {
+    let mut ranking: Vec<Vec<usize>> = last_round
+        .iter()
+        .sorted_by(|a, b| b.1.total_cmp(&a.1))
+        .group_by(|(_, votes)| votes.to_bits())
+        .into_iter()
+        .map(|(_, group)| group.map(|(candidate, _)| *candidate).collect())
+        .collect();
+
+    ranking.extend(
+        eliminated
+            .iter()
+            .rev()
+            .filter(|group| !group.is_empty())
+            .cloned(),
+    );
+
+    ranking
+}
+
+/// the candidates to eliminate from tallies sorted most first - candidates tied for the last
+/// place go together while their votes added up stay below the next candidate, otherwise
+/// `ties` decides who goes and nobody does while they are still tied
+pub fn eliminate_lowest(
+    tallies: &[(usize, f64)],
+    previous: &[Vec<(usize, f64)>],
+    ties: &mut Ties,
+) -> (Vec<usize>, Option<String>) {
+    let Some((_, lowest)) = tallies.last() else {
+        return (vec![], None);
+    };
+
+    let tied: Vec<usize> = tallies
+        .iter()
+        .filter(|(_, votes)| votes == lowest)
+        .map(|(candidate, _)| *candidate)
+        .collect();
+
+    let next = tallies
+        .iter()
+        .rev()
+        .map(|(_, votes)| *votes)
+        .find(|votes| votes > lowest);
+
+    match next {
+        _ if tied.len() == 1 => (tied, None),
+        Some(next) if lowest * (tied.len() as f64) < next => (tied, None),
+        _ => {
+            let (order, note) = ties(&tied, previous);
+            (last_place(&order).into_iter().collect(), Some(note))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::counting::{elect_top, elimination_ranking, Method};
+
+    #[test]
+    fn parse_method() {
+        for method in Method::ALL {
+            assert_eq!(method.to_string().parse::<Method>().unwrap(), *method);
+        }
+
+        assert_eq!("IRV".parse::<Method>().unwrap(), Method::Irv);
+        assert!("huff".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn every_kind_has_its_default() {
+        for kind in [
+            BallotKind::Ranked,
+            BallotKind::Approval,
+            BallotKind::Score,
+            BallotKind::Grades,
+            BallotKind::Cumulative,
+            BallotKind::Quadratic,
+        ] {
+            assert!(Method::default_for(kind).reads(kind));
+        }
+
+        assert!(!Method::Irv.reads(BallotKind::Approval));
+        assert!(!Method::reading(BallotKind::Score).contains(&Method::Irv));
+        assert!(!Method::Positional.reads(BallotKind::Grades));
+        assert!(Method::reading(BallotKind::Grades)
+            .iter()
+            .all(|method| *method == Method::MajorityJudgment));
+    }
+
+    #[test]
+    fn elect_top_stops_at_ties() {
+        let ranking = [vec![2], vec![0, 1], vec![3]];
+
+        assert_eq!(elect_top(&ranking, 1), vec![2]);
+        assert_eq!(elect_top(&ranking, 2), vec![2]);
+        assert_eq!(elect_top(&ranking, 3), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn elimination_ranking_returns_correct() {
+        let ranking = elimination_ranking(
+            &[(1, 2.0), (3, 5.0), (4, 2.0)],
+            &[vec![0], vec![], vec![2, 5]],
+        );
+
+        assert_eq!(ranking, vec![vec![3], vec![1, 4], vec![2, 5], vec![0]]);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::voting::counting::{elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// how list totals are turned into seats
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Apportionment {
+    /// highest averages with divisors 1, 2, 3...
+    DHondt,
+    /// highest averages with divisors 1, 3, 5...
+    SainteLague,
+    /// hare quota, remaining seats by largest remainder
+    HareNiemeyer,
+}
+
+/// seats per list - ties go to the earlier list, without any votes no seats are given
+pub fn allocate(apportionment: Apportionment, totals: &[f64], seats: usize) -> Vec<usize> {
+    let mut allocated = vec![0; totals.len()];
+    let sum: f64 = totals.iter().sum();
+
+    if sum <= 0.0 {
+        return allocated;
+    }
+
+    match apportionment {
+        Apportionment::DHondt | Apportionment::SainteLague => {
+            let divisor = |seats: usize| match apportionment {
+                Apportionment::SainteLague => (2 * seats + 1) as f64,
+                _ => (seats + 1) as f64,
+            };
+
+            for _ in 0..seats {
+                let (list, _) = totals
+                    .iter()
+                    .enumerate()
+                    .map(|(list, total)| (list, total / divisor(allocated[list])))
+                    .rev()
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .unwrap();
+
+                allocated[list] += 1;
+            }
+        }
+        Apportionment::HareNiemeyer => {
+            let quotas: Vec<f64> = totals
+                .iter()
+                .map(|total| total * seats as f64 / sum)
+                .collect();
+
+            for (list, quota) in quotas.iter().enumerate() {
+                allocated[list] = quota.floor() as usize;
+            }
+
+            let remaining = seats - allocated.iter().sum::<usize>();
+
+            for list in (0..totals.len())
+                .sorted_by(|a, b| quotas[*b].fract().total_cmp(&quotas[*a].fract()))
+                .take(remaining)
+            {
+                allocated[list] += 1;
+            }
+        }
+    }
+
+    allocated
+}
+
+/// first preferences count for the list of the candidate, seats are filled
+/// in the order the candidates are listed - candidates without a party stand alone
+pub struct PartyList(pub Apportionment);
+
+impl CountingMethod for PartyList {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let lists: Vec<String> = voting
+            .candidates
+            .iter()
+            .map(|candidate| candidate.party.clone().unwrap_or(candidate.name.clone()))
+            .unique()
+            .collect();
+
+        let members = |list: &String| -> Vec<usize> {
+            (0..voting.candidates.len())
+                .filter(|candidate| {
+                    let candidate = &voting.candidates[*candidate];
+                    candidate.party.as_ref().unwrap_or(&candidate.name) == list
+                })
+                .collect()
+        };
+
+        let totals: Vec<f64> = lists
+            .iter()
+            .map(|list| {
+                members(list)
+                    .iter()
+                    .map(|candidate| voting.candidates[*candidate].get_first_votes() as f64)
+                    .sum()
+            })
+            .collect();
+
+        let seats = allocate(self.0, &totals, voting.seats);
+
+        let mut elected = vec![];
+        let mut notes = vec![];
+
+        for (list, name) in lists.iter().enumerate() {
+            let members = members(name);
+            let filled: Vec<usize> = members.iter().take(seats[list]).copied().collect();
+
+            notes.push(format!(
+                "{} votes - {} seats - {}",
+                totals[list], seats[list], name
+            ));
+
+            if filled.len() < seats[list] {
+                notes.push(format!(
+                    "{} seats of {} stay empty - not enough candidates",
+                    seats[list] - filled.len(),
+                    name
+                ));
+            }
+
+            elected.extend(filled);
+        }
+
+        let others: Vec<(usize, f64)> = (0..voting.candidates.len())
+            .filter(|candidate| !elected.contains(candidate))
+            .map(|candidate| {
+                (
+                    candidate,
+                    voting.candidates[candidate].get_first_votes() as f64,
+                )
+            })
+            .collect();
+
+        // the elected are ordered by list and seat, they are not tied
+        let mut ranking: Vec<Vec<usize>> =
+            elected.iter().map(|candidate| vec![*candidate]).collect();
+        ranking.extend(elimination_ranking(&others, &[]));
+
+        CountResult {
+            rounds: vec![Round {
+                title: "Lists".to_string(),
+                tallies: elected
+                    .iter()
+                    .map(|candidate| {
+                        (
+                            *candidate,
+                            voting.candidates[*candidate].get_first_votes() as f64,
+                        )
+                    })
+                    .collect(),
+                elected: elected.clone(),
+                notes,
+                ..Round::default()
+            }],
+            ranking,
+            elected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::party_list::{allocate, Apportionment, PartyList};
+    use crate::voting::counting::CountingMethod;
+    use crate::voting::tie_break::TieBreaker;
+    use crate::voting::Voting;
+
+    #[test]
+    fn dhondt() {
+        let totals = [100_000.0, 80_000.0, 30_000.0, 20_000.0];
+
+        assert_eq!(
+            allocate(Apportionment::DHondt, &totals, 8),
+            vec![4, 3, 1, 0]
+        );
+    }
+
+    #[test]
+    fn sainte_lague() {
+        let totals = [100_000.0, 80_000.0, 30_000.0, 20_000.0];
+
+        assert_eq!(
+            allocate(Apportionment::SainteLague, &totals, 8),
+            vec![3, 3, 1, 1]
+        );
+    }
+
+    #[test]
+    fn hare_niemeyer() {
+        let totals = [47.0, 16.0, 15.8, 12.0, 6.1, 3.1];
+
+        assert_eq!(
+            allocate(Apportionment::HareNiemeyer, &totals, 10),
+            vec![5, 2, 1, 1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn ties_go_to_earlier_list() {
+        assert_eq!(
+            allocate(Apportionment::DHondt, &[10.0, 10.0], 1),
+            vec![1, 0]
+        );
+    }
+
+    #[test]
+    fn no_votes_no_seats() {
+        for apportionment in [
+            Apportionment::DHondt,
+            Apportionment::SainteLague,
+            Apportionment::HareNiemeyer,
+        ] {
+            assert_eq!(allocate(apportionment, &[0.0, 0.0], 2), vec![0, 0]);
+            assert_eq!(allocate(apportionment, &[], 2), Vec::<usize>::new());
+        }
+    }
+
+    #[test]
+    fn elected_are_ranked_by_list_and_seat() {
+        let mut candidates: Vec<Candidate> = ["huff", "puff", "muff"]
+            .iter()
+            .map(|name| Candidate::new(name.to_string(), 1))
+            .collect();
+        candidates[0].party = Some("wolves".to_string());
+        candidates[1].party = Some("wolves".to_string());
+        candidates[2].party = Some("pigs".to_string());
+        candidates[0].votes = vec![3];
+        candidates[2].votes = vec![2];
+
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+        voting.seats = 3;
+
+        let result = PartyList(Apportionment::DHondt).count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.elected, vec![0, 1, 2]);
+        assert_eq!(result.ranking, vec![vec![0], vec![1], vec![2]]);
+
+        for candidate in &mut voting.candidates {
+            candidate.votes = vec![0];
+        }
+
+        let result = PartyList(Apportionment::DHondt).count(&voting, &mut TieBreaker::new(&voting));
+
+        assert!(result.elected.is_empty());
+        assert_eq!(result.ranking, vec![vec![0, 1, 2]]);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::voting::ballot::BallotKind;
+use crate::voting::counting::{elect_top, elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+
+/// ranks the options by the votes bought with each voter's budget
+pub struct Points;
+
+impl CountingMethod for Points {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let tallies: Vec<(usize, f64)> = voting
+            .point_totals()
+            .iter()
+            .enumerate()
+            .map(|(candidate, points)| (candidate, *points as f64))
+            .collect();
+
+        let ranking = elimination_ranking(&tallies, &[]);
+
+        let budget = match voting.ballot_kind {
+            BallotKind::Quadratic => {
+                format!("{} credits per paper - n votes cost n²", voting.budget)
+            }
+            _ => format!("{} points per paper", voting.budget),
+        };
+
+        let round = Round {
+            title: "Points".to_string(),
+            tallies: ranking
+                .iter()
+                .flatten()
+                .map(|candidate| tallies[*candidate])
+                .collect(),
+            notes: vec![budget],
+            ..Round::default()
+        };
+
+        CountResult {
+            rounds: vec![round],
+            elected: elect_top(&ranking, voting.seats),
+            ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::points::Points;
+    use crate::voting::counting::CountingMethod;
+    use crate::voting::tie_break::TieBreaker;
+    use crate::voting::Voting;
+
+    #[test]
+    fn most_points_win() {
+        let candidates = vec![
+            Candidate::new("roof".to_string(), 1),
+            Candidate::new("park".to_string(), 1),
+            Candidate::new("road".to_string(), 1),
+        ];
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+        voting.ballot_kind = BallotKind::Cumulative;
+        voting.budget = 5;
+
+        voting.set_points(0, Some(5));
+        voting.vote();
+
+        voting.set_points(1, Some(3));
+        voting.set_points(2, Some(2));
+        voting.vote();
+
+        voting.set_points(1, Some(3));
+        voting.vote();
+
+        let result = Points.count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.rounds[0].tallies, vec![(1, 6.0), (0, 5.0), (2, 2.0)]);
+        assert_eq!(result.ranking, vec![vec![1], vec![0], vec![2]]);
+        assert_eq!(result.elected, vec![1]);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::voting::counting::{elect_top, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+/// ranks by the points of the configured weighting, first votes decide equal points
+pub struct Positional;
+
+impl CountingMethod for Positional {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let scores = voting.scores();
+        let first_votes: Vec<usize> = voting
+            .candidates
+            .iter()
+            .map(|candidate| candidate.get_first_votes())
+            .collect();
+
+        let order: Vec<usize> = (0..voting.candidates.len())
+            .sorted_by(|a, b| {
+                scores[*b]
+                    .total_cmp(&scores[*a])
+                    .then(first_votes[*b].cmp(&first_votes[*a]))
+            })
+            .collect();
+
+        let ranking: Vec<Vec<usize>> = order
+            .iter()
+            .group_by(|candidate| (scores[**candidate].to_bits(), first_votes[**candidate]))
+            .into_iter()
+            .map(|(_, group)| group.copied().collect())
+            .collect();
+
+        let points = Round {
+            title: format!("Points - {}", voting.weighting),
+            tallies: order
+                .iter()
+                .map(|candidate| (*candidate, scores[*candidate]))
+                .collect(),
+            ..Round::default()
+        };
+
+        let first = Round {
+            title: "First votes".to_string(),
+            tallies: order
+                .iter()
+                .map(|candidate| (*candidate, first_votes[*candidate] as f64))
+                .collect(),
+            ..Round::default()
+        };
+
+        CountResult {
+            rounds: vec![points, first],
+            elected: elect_top(&ranking, voting.seats),
+            ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::positional::Positional;
+    use crate::voting::counting::CountingMethod;
+    use crate::voting::tie_break::TieBreaker;
+    use crate::voting::Voting;
+
+    #[test]
+    fn first_votes_decide_equal_points() {
+        let candidates = vec![
+            Candidate::new("huff".to_string(), 2),
+            Candidate::new("puff".to_string(), 2),
+            Candidate::new("muff".to_string(), 2),
+        ];
+        let mut voting = Voting::new(candidates, "test.txt", 2).unwrap();
+
+        for names in [["h", "p"], ["h", "p"], ["p", "m"]] {
+            voting.candidate_selections[0].search_text = names[0].to_string();
+            voting.candidate_selections[1].search_text = names[1].to_string();
+            voting.vote();
+        }
+
+        // huff and puff have 4 points each, huff has more first votes
+        let result = Positional.count(&voting, &mut TieBreaker::new(&voting));
+
+        assert_eq!(result.ranking, vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(result.elected, vec![0]);
+        assert_eq!(result.rounds[0].tallies[0], (0, 4.0));
+    }
+}
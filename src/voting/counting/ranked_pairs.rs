@@ -0,0 +1,277 @@
+use crate::voting::counting::condorcet::PairwiseMatrix;
+use crate::voting::counting::{elect_top, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::{TieBreaker, Ties};
+use crate::voting::Voting;
+use iter_tools::Itertools;
+use std::cmp::Reverse;
+
+pub struct RankedPairs;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Victory {
+    pub winner: usize,
+    pub loser: usize,
+    /// ballots preferring the winner
+    pub votes: usize,
+    /// ballots preferring the loser
+    pub against: usize,
+}
+
+pub struct RankedPairsResult {
+    /// victories in the order they were locked
+    pub locked: Vec<Victory>,
+    /// victories that would have created a cycle
+    pub skipped: Vec<Victory>,
+    /// victories left unlocked because equally strong ones contradict each other
+    /// and the tie break could not order them
+    pub unlocked: Vec<Victory>,
+    /// how equally strong victories were ordered
+    pub tie_breaks: Vec<String>,
+    /// groups of tied candidates, winners first
+    pub ranking: Vec<Vec<usize>>,
+}
+
+/// locks the victories from strongest to weakest (most winning votes, then fewest
+/// opposing votes) unless they would contradict an already locked one
+///
+/// when equally strong victories contradict each other `ties` orders their candidates -
+/// the victory of the better winner comes first, then the one over the worse loser,
+/// locking stops while they are still tied
+pub fn count(matrix: &PairwiseMatrix, ties: &mut Ties) -> RankedPairsResult {
+    let size = matrix.size();
+
+    let victories = (0..size)
+        .cartesian_product(0..size)
+        .filter(|(a, b)| matrix.beats(*a, *b))
+        .map(|(winner, loser)| Victory {
+            winner,
+            loser,
+            votes: matrix.preferences[winner][loser],
+            against: matrix.preferences[loser][winner],
+        })
+        .sorted_by(|a, b| b.votes.cmp(&a.votes).then(a.against.cmp(&b.against)))
+        .group_by(|victory| (victory.votes, victory.against));
+
+    let mut locked: Vec<Victory> = vec![];
+    let mut skipped = vec![];
+    let mut unlocked = vec![];
+    let mut tie_breaks = vec![];
+
+    for (_, group) in &victories {
+        let mut group: Vec<Victory> = group.collect();
+
+        if !unlocked.is_empty() {
+            unlocked.extend(group);
+            continue;
+        }
+
+        // the order only matters when locking all of them would create a cycle
+        if group.len() > 1 && !acyclic(&[locked.clone(), group.clone()].concat()) {
+            let candidates: Vec<usize> = group
+                .iter()
+                .flat_map(|victory| [victory.winner, victory.loser])
+                .unique()
+                .sorted()
+                .collect();
+
+            let (order, note) = ties(&candidates, &[]);
+            tie_breaks.push(note);
+
+            if order.len() < candidates.len() {
+                unlocked.extend(group);
+                continue;
+            }
+
+            let place = |candidate: usize| order.iter().position(|group| group[0] == candidate);
+            group.sort_by_key(|victory| (place(victory.winner), Reverse(place(victory.loser))));
+        }
+
+        for victory in group {
+            if reaches(&locked, victory.loser, victory.winner) {
+                skipped.push(victory);
+            } else {
+                locked.push(victory);
+            }
+        }
+    }
+
+    let mut remaining: Vec<usize> = (0..size).collect();
+    let mut ranking = vec![];
+
+    while !remaining.is_empty() {
+        let sources: Vec<usize> = remaining
+            .iter()
+            .filter(|candidate| {
+                !locked.iter().any(|victory| {
+                    victory.loser == **candidate && remaining.contains(&victory.winner)
+                })
+            })
+            .copied()
+            .collect();
+
+        remaining.retain(|candidate| !sources.contains(candidate));
+        ranking.push(sources);
+    }
+
+    RankedPairsResult {
+        locked,
+        skipped,
+        unlocked,
+        tie_breaks,
+        ranking,
+    }
+}
+
+impl CountingMethod for RankedPairs {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult {
+        let matrix = PairwiseMatrix::new(voting.candidates.len(), &voting.rankings());
+        let result = count(&matrix, &mut |group, previous| ties.order(group, previous));
+
+        let names = voting.candidate_names();
+        let describe = |victory: &Victory| {
+            format!(
+                "{}:{} - {} over {}",
+                victory.votes, victory.against, names[victory.winner], names[victory.loser]
+            )
+        };
+
+        let locked = Round {
+            title: "Locked".to_string(),
+            notes: result
+                .locked
+                .iter()
+                .map(describe)
+                .chain(result.tie_breaks.clone())
+                .collect(),
+            ..Round::default()
+        };
+
+        let skipped = Round {
+            title: "Skipped - would create a cycle".to_string(),
+            notes: result.skipped.iter().map(describe).collect(),
+            ..Round::default()
+        };
+
+        let mut rounds = vec![locked, skipped];
+
+        if !result.unlocked.is_empty() {
+            rounds.push(Round {
+                title: "Not locked - still tied".to_string(),
+                notes: result.unlocked.iter().map(describe).collect(),
+                ..Round::default()
+            });
+        }
+
+        CountResult {
+            rounds,
+            elected: elect_top(&result.ranking, voting.seats),
+            ranking: result.ranking,
+        }
+    }
+}
+
+/// whether the victories contain no cycle
+fn acyclic(victories: &[Victory]) -> bool {
+    victories
+        .iter()
+        .all(|victory| !reaches(victories, victory.loser, victory.winner))
+}
+
+/// whether `to` can be reached from `from` over the locked victories
+fn reaches(locked: &[Victory], from: usize, to: usize) -> bool {
+    let mut visited = vec![from];
+    let mut stack = vec![from];
+
+    while let Some(current) = stack.pop() {
+        if current == to {
+            return true;
+        }
+
+        for victory in locked.iter().filter(|victory| victory.winner == current) {
+            if !visited.contains(&victory.loser) {
+                visited.push(victory.loser);
+                stack.push(victory.loser);
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::condorcet::PairwiseMatrix;
+    use crate::voting::counting::ranked_pairs::count;
+
+    fn tied(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        (vec![group.to_vec()], "still tied".to_string())
+    }
+
+    #[test]
+    fn skips_weakest_link_of_cycle() {
+        let mut rankings = vec![];
+        rankings.extend(vec![vec![0, 1, 2]; 4]);
+        rankings.extend(vec![vec![1, 2, 0]; 3]);
+        rankings.extend(vec![vec![2, 0, 1]; 2]);
+
+        // 0 > 1 (6:3), 1 > 2 (7:2), 2 > 0 (5:4)
+        let result = count(&PairwiseMatrix::new(3, &rankings), &mut tied);
+
+        assert_eq!(result.locked.len(), 2);
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!((result.skipped[0].winner, result.skipped[0].loser), (2, 0));
+        assert_eq!(result.ranking, vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn locks_in_order_of_strength() {
+        let rankings = [vec![0, 1, 2], vec![0, 1, 2], vec![1, 0, 2]];
+
+        let result = count(&PairwiseMatrix::new(3, &rankings), &mut tied);
+
+        assert_eq!(result.locked[0].votes, 3);
+        assert_eq!(result.locked.last().unwrap().votes, 2);
+        assert!(result.skipped.is_empty());
+        assert_eq!(result.ranking[0], vec![0]);
+    }
+
+    #[test]
+    fn unbeaten_candidates_tie() {
+        let result = count(
+            &PairwiseMatrix::new(2, &[vec![0, 1], vec![1, 0]]),
+            &mut tied,
+        );
+
+        assert!(result.locked.is_empty());
+        assert_eq!(result.ranking, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn equal_victories_in_a_cycle_go_to_the_tie_break() {
+        let rankings = [vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]];
+
+        // 0 > 1, 1 > 2 and 2 > 0 all win 2:1
+        let matrix = PairwiseMatrix::new(3, &rankings);
+
+        let result = count(&matrix, &mut tied);
+
+        assert!(result.locked.is_empty());
+        assert_eq!(result.unlocked.len(), 3);
+        assert_eq!(result.tie_breaks, vec!["still tied"]);
+        assert_eq!(result.ranking, vec![vec![0, 1, 2]]);
+
+        let result = count(&matrix, &mut |group, _| {
+            let order = group
+                .iter()
+                .rev()
+                .map(|candidate| vec![*candidate])
+                .collect();
+            (order, "decided".to_string())
+        });
+
+        // 2 before 1 before 0 - 2 > 0 and 1 > 2 are locked, 0 > 1 would close the cycle
+        assert_eq!(result.tie_breaks, vec!["decided"]);
+        assert_eq!((result.skipped[0].winner, result.skipped[0].loser), (0, 1));
+        assert_eq!(result.ranking, vec![vec![1], vec![2], vec![0]]);
+    }
+}
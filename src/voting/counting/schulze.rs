@@ -0,0 +1,144 @@
+use crate::voting::counting::condorcet::{matrix_lines, PairwiseMatrix};
+use crate::voting::counting::{elect_top, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::TieBreaker;
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+pub struct Schulze;
+
+pub struct SchulzeResult {
+    /// `strongest_paths[a][b]` is the strength of the strongest path from a to b
+    pub strongest_paths: Vec<Vec<usize>>,
+    /// groups of tied candidates, winners first
+    pub ranking: Vec<Vec<usize>>,
+}
+
+pub fn count(matrix: &PairwiseMatrix) -> SchulzeResult {
+    let size = matrix.size();
+    let d = &matrix.preferences;
+
+    let mut paths = vec![vec![0; size]; size];
+
+    for a in 0..size {
+        for b in 0..size {
+            if a != b && d[a][b] > d[b][a] {
+                paths[a][b] = d[a][b];
+            }
+        }
+    }
+
+    for via in 0..size {
+        for a in 0..size {
+            if a == via {
+                continue;
+            }
+
+            for b in 0..size {
+                if b != a && b != via {
+                    paths[a][b] = paths[a][b].max(paths[a][via].min(paths[via][b]));
+                }
+            }
+        }
+    }
+
+    let wins: Vec<usize> = (0..size)
+        .map(|a| (0..size).filter(|b| paths[a][*b] > paths[*b][a]).count())
+        .collect();
+
+    let ranking = (0..size)
+        .sorted_by(|a, b| wins[*b].cmp(&wins[*a]))
+        .group_by(|candidate| wins[*candidate])
+        .into_iter()
+        .map(|(_, group)| group.collect())
+        .collect();
+
+    SchulzeResult {
+        strongest_paths: paths,
+        ranking,
+    }
+}
+
+impl CountingMethod for Schulze {
+    fn count(&self, voting: &Voting, _ties: &mut TieBreaker) -> CountResult {
+        let matrix = PairwiseMatrix::new(voting.candidates.len(), &voting.rankings());
+        let result = count(&matrix);
+
+        let names = voting.candidate_names();
+        let name = |candidate: usize| names[candidate].to_string();
+
+        let head_to_head = Round {
+            title: "Head to head".to_string(),
+            notes: matrix_lines(&names, &matrix.preferences)
+                .into_iter()
+                .chain([
+                    matrix
+                        .condorcet_winner()
+                        .map(|winner| format!("condorcet winner - {}", name(winner)))
+                        .unwrap_or("no condorcet winner".to_string()),
+                    matrix
+                        .condorcet_loser()
+                        .map(|loser| format!("condorcet loser - {}", name(loser)))
+                        .unwrap_or("no condorcet loser".to_string()),
+                ])
+                .collect(),
+            ..Round::default()
+        };
+
+        let strongest_paths = Round {
+            title: "Strongest paths".to_string(),
+            notes: matrix_lines(&names, &result.strongest_paths),
+            ..Round::default()
+        };
+
+        CountResult {
+            rounds: vec![head_to_head, strongest_paths],
+            elected: elect_top(&result.ranking, voting.seats),
+            ranking: result.ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::condorcet::PairwiseMatrix;
+    use crate::voting::counting::schulze::count;
+
+    #[test]
+    fn follows_condorcet_winner() {
+        let matrix = PairwiseMatrix::new(3, &[vec![1, 0, 2], vec![1, 2, 0], vec![0, 1, 2]]);
+
+        let result = count(&matrix);
+
+        assert_eq!(result.ranking, vec![vec![1], vec![0], vec![2]]);
+    }
+
+    #[test]
+    fn resolves_cycle() {
+        // wikipedia example with 45 voters and candidates A-E
+        let mut rankings = vec![];
+        rankings.extend(vec![vec![0, 2, 1, 4, 3]; 5]);
+        rankings.extend(vec![vec![0, 3, 4, 2, 1]; 5]);
+        rankings.extend(vec![vec![1, 4, 3, 0, 2]; 8]);
+        rankings.extend(vec![vec![2, 0, 1, 4, 3]; 3]);
+        rankings.extend(vec![vec![2, 0, 4, 1, 3]; 7]);
+        rankings.extend(vec![vec![2, 1, 0, 3, 4]; 2]);
+        rankings.extend(vec![vec![3, 2, 4, 1, 0]; 7]);
+        rankings.extend(vec![vec![4, 1, 0, 3, 2]; 8]);
+
+        let matrix = PairwiseMatrix::new(5, &rankings);
+        let result = count(&matrix);
+
+        assert_eq!(matrix.condorcet_winner(), None);
+        assert_eq!(
+            result.ranking,
+            vec![vec![4], vec![0], vec![2], vec![1], vec![3]]
+        );
+    }
+
+    #[test]
+    fn keeps_ties_together() {
+        let matrix = PairwiseMatrix::new(2, &[vec![0, 1], vec![1, 0]]);
+
+        assert_eq!(count(&matrix).ranking, vec![vec![0, 1]]);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::voting::counting::{elect_top, elimination_ranking, CountResult, CountingMethod, Round};
+use crate::voting::tie_break::{TieBreaker, Ties};
+use crate::voting::Voting;
+use iter_tools::Itertools;
+use std::cmp::Ordering;
+
+/// score then automatic runoff - the two highest scorers face each other
+/// and every ballot supports the one it gave more stars
+pub struct Star;
+
+pub struct StarResult {
+    /// (candidate index, stars) most stars first
+    pub totals: Vec<(usize, usize)>,
+    /// the finalists with the ballots preferring them
+    pub runoff: Vec<(usize, usize)>,
+    /// ballots scoring both finalists equally
+    pub no_preference: usize,
+    pub winner: Option<usize>,
+    /// how a tie for a place in the runoff was decided
+    pub tie_break: Option<String>,
+}
+
+/// ties for a place in the runoff are decided by `ties` - without finalists nobody wins
+pub fn count(candidate_count: usize, ballots: &[Vec<u8>], ties: &mut Ties) -> StarResult {
+    let mut stars = vec![0; candidate_count];
+
+    for ballot in ballots {
+        for (candidate, score) in ballot.iter().enumerate() {
+            stars[candidate] += *score as usize;
+        }
+    }
+
+    let totals: Vec<(usize, usize)> = (0..candidate_count)
+        .map(|candidate| (candidate, stars[candidate]))
+        .sorted_by(|a, b| b.1.cmp(&a.1))
+        .collect();
+
+    if totals.len() < 2 {
+        return StarResult {
+            winner: totals.first().map(|(candidate, _)| *candidate),
+            totals,
+            runoff: vec![],
+            no_preference: 0,
+            tie_break: None,
+        };
+    }
+
+    let (finalists, tie_break) = finalists(&totals, ties);
+
+    let [first, second] = finalists[..] else {
+        return StarResult {
+            totals,
+            runoff: vec![],
+            no_preference: 0,
+            winner: None,
+            tie_break,
+        };
+    };
+
+    let mut runoff = [(first, 0), (second, 0)];
+    let mut no_preference = 0;
+
+    for ballot in ballots {
+        match ballot[first].cmp(&ballot[second]) {
+            Ordering::Greater => runoff[0].1 += 1,
+            Ordering::Less => runoff[1].1 += 1,
+            Ordering::Equal => no_preference += 1,
+        }
+    }
+
+    // equal preferences go to the higher scorer
+    let winner = match runoff[0].1.cmp(&runoff[1].1) {
+        Ordering::Greater => Some(first),
+        Ordering::Less => Some(second),
+        Ordering::Equal => match stars[first].cmp(&stars[second]) {
+            Ordering::Equal => None,
+            _ => Some(first),
+        },
+    };
+
+    StarResult {
+        totals,
+        runoff: runoff.to_vec(),
+        no_preference,
+        winner,
+        tie_break,
+    }
+}
+
+/// the two highest scorers - empty while the tie for the second place is not decided
+fn finalists(totals: &[(usize, usize)], ties: &mut Ties) -> (Vec<usize>, Option<String>) {
+    let second = totals[1].1;
+
+    let ahead: Vec<usize> = totals
+        .iter()
+        .filter(|(_, stars)| *stars > second)
+        .map(|(candidate, _)| *candidate)
+        .collect();
+    let tied: Vec<usize> = totals
+        .iter()
+        .filter(|(_, stars)| *stars == second)
+        .map(|(candidate, _)| *candidate)
+        .collect();
+
+    if ahead.len() + tied.len() == 2 {
+        return ([ahead, tied].concat(), None);
+    }
+
+    let tallies: Vec<(usize, f64)> = totals
+        .iter()
+        .map(|(candidate, stars)| (*candidate, *stars as f64))
+        .collect();
+
+    let (order, note) = ties(&tied, &[tallies]);
+    let places = 2 - ahead.len();
+    let picked = elect_top(&order, places);
+
+    if picked.len() < places {
+        return (vec![], Some(note));
+    }
+
+    ([ahead, picked].concat(), Some(note))
+}
+
+impl CountingMethod for Star {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult {
+        let result = count(
+            voting.candidates.len(),
+            &voting.star_ballots(),
+            &mut |group, previous| ties.order(group, previous),
+        );
+
+        let scores = Round {
+            title: "Scores".to_string(),
+            tallies: result
+                .totals
+                .iter()
+                .map(|(candidate, stars)| (*candidate, *stars as f64))
+                .collect(),
+            notes: result.tie_break.clone().into_iter().collect(),
+            ..Round::default()
+        };
+
+        let runoff = Round {
+            title: "Automatic runoff".to_string(),
+            tallies: result
+                .runoff
+                .iter()
+                .map(|(candidate, preferences)| (*candidate, *preferences as f64))
+                .collect(),
+            elected: result.winner.into_iter().collect(),
+            notes: vec![format!("{} no preference", result.no_preference)],
+            ..Round::default()
+        };
+
+        let mut ranking: Vec<Vec<usize>> = match result.winner {
+            Some(winner) => {
+                let runner_up = result
+                    .runoff
+                    .iter()
+                    .find(|(candidate, _)| *candidate != winner)
+                    .map(|(candidate, _)| *candidate);
+                [Some(winner), runner_up]
+                    .into_iter()
+                    .flatten()
+                    .map(|candidate| vec![candidate])
+                    .collect()
+            }
+            None => vec![result
+                .runoff
+                .iter()
+                .map(|(candidate, _)| *candidate)
+                .collect()],
+        };
+
+        ranking.retain(|group| !group.is_empty());
+
+        let finalists: Vec<usize> = ranking.iter().flatten().copied().collect();
+        let others: Vec<(usize, f64)> = result
+            .totals
+            .iter()
+            .filter(|(candidate, _)| !finalists.contains(candidate))
+            .map(|(candidate, stars)| (*candidate, *stars as f64))
+            .collect();
+
+        ranking.extend(elimination_ranking(&others, &[]));
+
+        let rounds = if result.runoff.is_empty() {
+            vec![scores]
+        } else {
+            vec![scores, runoff]
+        };
+
+        CountResult {
+            rounds,
+            elected: result.winner.into_iter().collect(),
+            ranking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::star::count;
+
+    fn tied(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        (vec![group.to_vec()], "still tied".to_string())
+    }
+
+    #[test]
+    fn runoff_can_overturn_scores() {
+        let ballots = [vec![5, 3, 0], vec![5, 3, 0], vec![0, 1, 0], vec![0, 1, 5]];
+
+        let result = count(3, &ballots, &mut tied);
+
+        assert_eq!(result.totals[0], (0, 10));
+        assert_eq!(result.runoff, vec![(0, 2), (1, 2)]);
+        assert_eq!(result.winner, Some(0));
+
+        let ballots = [vec![5, 0, 0], vec![0, 1, 0], vec![0, 1, 0], vec![1, 2, 0]];
+
+        let result = count(3, &ballots, &mut tied);
+
+        assert_eq!(result.totals[0], (0, 6));
+        assert_eq!(result.runoff, vec![(0, 1), (1, 3)]);
+        assert_eq!(result.winner, Some(1));
+    }
+
+    #[test]
+    fn counts_no_preference() {
+        let ballots = [vec![3, 3], vec![5, 0]];
+
+        let result = count(2, &ballots, &mut tied);
+
+        assert_eq!(result.no_preference, 1);
+        assert_eq!(result.winner, Some(0));
+    }
+
+    #[test]
+    fn full_tie_has_no_winner() {
+        let result = count(2, &[vec![5, 0], vec![0, 5]], &mut tied);
+
+        assert_eq!(result.winner, None);
+    }
+
+    #[test]
+    fn tied_finalists_go_to_the_tie_break() {
+        let ballots = [vec![5, 3, 3], vec![0, 1, 1]];
+
+        let result = count(3, &ballots, &mut tied);
+
+        assert!(result.runoff.is_empty());
+        assert_eq!(result.winner, None);
+        assert_eq!(result.tie_break, Some("still tied".to_string()));
+
+        let result = count(3, &ballots, &mut |group, _| {
+            let order = group
+                .iter()
+                .rev()
+                .map(|candidate| vec![*candidate])
+                .collect();
+            (order, "decided".to_string())
+        });
+
+        assert_eq!(result.runoff, vec![(0, 1), (2, 1)]);
+        assert_eq!(result.winner, Some(0));
+    }
+}
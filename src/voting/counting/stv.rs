@@ -0,0 +1,301 @@
+use crate::voting::counting::{
+    eliminate_lowest, elimination_ranking, CountResult, CountingMethod, Round,
+};
+use crate::voting::tie_break::{TieBreaker, Ties};
+use crate::voting::Voting;
+use iter_tools::Itertools;
+
+const EPSILON: f64 = 1e-9;
+
+pub struct StvRound {
+    /// (candidate index, votes) of every continuing candidate, most votes first
+    pub tallies: Vec<(usize, f64)>,
+    pub exhausted: f64,
+    pub elected: Vec<usize>,
+    pub eliminated: Vec<usize>,
+    /// how a tie for the last place was decided
+    pub tie_break: Option<String>,
+}
+
+pub struct StvResult {
+    pub quota: f64,
+    pub rounds: Vec<StvRound>,
+    pub elected: Vec<usize>,
+}
+
+pub struct SingleTransferableVote;
+
+/// the droop quota - the smallest number of votes only `seats` candidates can reach
+pub fn droop_quota(valid: usize, seats: usize) -> f64 {
+    (valid / (seats + 1) + 1) as f64
+}
+
+/// counts the rankings (candidate indices, best first) by single transferable vote
+///
+/// surpluses are transferred fractionally: every ballot held by an elected candidate
+/// moves on with its weight multiplied by `surplus / votes`, ties for the last place
+/// are decided by `ties` - the count stops while they are still tied
+pub fn count(
+    candidate_count: usize,
+    rankings: &[Vec<usize>],
+    seats: usize,
+    ties: &mut Ties,
+) -> StvResult {
+    let quota = droop_quota(rankings.len(), seats);
+
+    let mut weights = vec![1.0_f64; rankings.len()];
+    let mut continuing: Vec<usize> = (0..candidate_count).collect();
+    let mut elected: Vec<usize> = vec![];
+    let mut rounds = vec![];
+
+    while elected.len() < seats && !continuing.is_empty() {
+        let holders: Vec<Option<usize>> = rankings
+            .iter()
+            .map(|ranking| {
+                ranking
+                    .iter()
+                    .find(|index| continuing.contains(index))
+                    .copied()
+            })
+            .collect();
+
+        let mut votes = vec![0.0_f64; candidate_count];
+        let mut exhausted = 0.0;
+
+        for (holder, weight) in holders.iter().zip(&weights) {
+            match holder {
+                Some(index) => votes[*index] += weight,
+                None => exhausted += weight,
+            }
+        }
+
+        let tallies: Vec<(usize, f64)> = continuing
+            .iter()
+            .map(|index| (*index, votes[*index]))
+            .sorted_by(|a, b| b.1.total_cmp(&a.1))
+            .collect();
+
+        let seats_left = seats - elected.len();
+
+        let newly_elected: Vec<usize> = if continuing.len() <= seats_left {
+            tallies.iter().map(|(index, _)| *index).collect()
+        } else {
+            tallies
+                .iter()
+                .filter(|(_, votes)| *votes >= quota - EPSILON)
+                .take(seats_left)
+                .map(|(index, _)| *index)
+                .collect()
+        };
+
+        if !newly_elected.is_empty() {
+            for candidate in &newly_elected {
+                if votes[*candidate] <= 0.0 {
+                    continue;
+                }
+
+                let surplus = (votes[*candidate] - quota).max(0.0);
+                let factor = surplus / votes[*candidate];
+
+                for (holder, weight) in holders.iter().zip(weights.iter_mut()) {
+                    if *holder == Some(*candidate) {
+                        *weight *= factor;
+                    }
+                }
+            }
+
+            continuing.retain(|index| !newly_elected.contains(index));
+            elected.extend(&newly_elected);
+
+            rounds.push(StvRound {
+                tallies,
+                exhausted,
+                elected: newly_elected,
+                eliminated: vec![],
+                tie_break: None,
+            });
+
+            continue;
+        }
+
+        let previous: Vec<Vec<(usize, f64)>> = rounds
+            .iter()
+            .map(|round| round.tallies.clone())
+            .chain([tallies.clone()])
+            .collect();
+
+        let (eliminated, tie_break) = eliminate_lowest(&tallies, &previous, ties);
+        continuing.retain(|index| !eliminated.contains(index));
+
+        let stuck = eliminated.is_empty();
+
+        rounds.push(StvRound {
+            tallies,
+            exhausted,
+            elected: vec![],
+            eliminated,
+            tie_break,
+        });
+
+        if stuck {
+            break;
+        }
+    }
+
+    StvResult {
+        quota,
+        rounds,
+        elected,
+    }
+}
+
+impl CountingMethod for SingleTransferableVote {
+    fn count(&self, voting: &Voting, ties: &mut TieBreaker) -> CountResult {
+        let result = count(
+            voting.candidates.len(),
+            &voting.rankings(),
+            voting.seats,
+            &mut |group, previous| ties.order(group, previous),
+        );
+
+        let rounds: Vec<Round> = result
+            .rounds
+            .iter()
+            .enumerate()
+            .map(|(index, round)| Round {
+                title: format!("Round {}", index + 1),
+                tallies: round.tallies.clone(),
+                elected: round.elected.clone(),
+                eliminated: round.eliminated.clone(),
+                notes: [
+                    format!("{} quota", result.quota),
+                    format!("{:.2} exhausted", round.exhausted),
+                ]
+                .into_iter()
+                .chain(round.tie_break.clone())
+                .collect(),
+            })
+            .collect();
+
+        let eliminated: Vec<Vec<usize>> = rounds
+            .iter()
+            .map(|round| round.eliminated.clone())
+            .collect();
+
+        let continuing: Vec<(usize, f64)> = rounds
+            .last()
+            .map(|round| {
+                round
+                    .tallies
+                    .iter()
+                    .filter(|(candidate, _)| {
+                        !result.elected.contains(candidate)
+                            && !eliminated.iter().flatten().any(|other| other == candidate)
+                    })
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut ranking: Vec<Vec<usize>> = result
+            .elected
+            .iter()
+            .map(|candidate| vec![*candidate])
+            .collect();
+        ranking.extend(elimination_ranking(&continuing, &eliminated));
+
+        CountResult {
+            rounds,
+            ranking,
+            elected: result.elected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::counting::stv::{count, droop_quota};
+
+    /// puts the tied candidates in the order they are listed
+    fn listed(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        let order = group.iter().map(|candidate| vec![*candidate]).collect();
+        (order, "listed".to_string())
+    }
+
+    fn tied(group: &[usize], _: &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) {
+        (vec![group.to_vec()], "still tied".to_string())
+    }
+
+    #[test]
+    fn quota() {
+        assert_eq!(droop_quota(100, 1), 51.0);
+        assert_eq!(droop_quota(100, 3), 26.0);
+        assert_eq!(droop_quota(7, 2), 3.0);
+    }
+
+    #[test]
+    fn transfers_surplus_fractionally() {
+        let mut rankings = vec![vec![0, 1]; 6];
+        rankings.extend(vec![vec![2]; 2]);
+        rankings.push(vec![3]);
+
+        // quota 4 - candidate 0 has a surplus of 2 spread over 6 ballots
+        let result = count(4, &rankings, 2, &mut tied);
+
+        assert_eq!(result.quota, 4.0);
+        assert_eq!(result.rounds[0].elected, vec![0]);
+
+        let (_, votes) = result.rounds[1]
+            .tallies
+            .iter()
+            .find(|(index, _)| *index == 1)
+            .unwrap();
+        assert!((votes - 2.0).abs() < 1e-9);
+
+        assert_eq!(result.elected.len(), 2);
+        assert_eq!(result.elected[0], 0);
+    }
+
+    #[test]
+    fn eliminates_and_tracks_exhausted() {
+        let rankings = [
+            vec![0],
+            vec![0],
+            vec![0],
+            vec![1],
+            vec![1],
+            vec![2],
+            vec![3, 1],
+            vec![4],
+        ];
+
+        // quota 3 - 0 is elected without surplus, then 4 and 3 are eliminated
+        let result = count(5, &rankings, 2, &mut listed);
+
+        assert_eq!(result.rounds[0].elected, vec![0]);
+        assert_eq!(result.rounds[1].eliminated, vec![4]);
+        assert_eq!(result.rounds[1].tie_break, Some("listed".to_string()));
+        assert_eq!(result.rounds[2].eliminated, vec![3]);
+        assert_eq!(result.rounds[2].exhausted, 1.0);
+        assert_eq!(result.elected, vec![0, 1]);
+    }
+
+    #[test]
+    fn fills_remaining_seats() {
+        let result = count(3, &[vec![0]], 3, &mut tied);
+
+        assert_eq!(result.elected.len(), 3);
+    }
+
+    #[test]
+    fn tied_last_places_stop_the_count() {
+        let rankings = [vec![0], vec![0], vec![0], vec![1], vec![2]];
+
+        // quota 2 - 0 is elected, then 1 and 2 are tied for the last seat
+        let result = count(3, &rankings, 2, &mut tied);
+
+        assert_eq!(result.elected, vec![0]);
+        assert!(result.rounds[1].eliminated.is_empty());
+        assert_eq!(result.rounds[1].tie_break, Some("still tied".to_string()));
+    }
+}
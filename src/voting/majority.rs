@@ -0,0 +1,202 @@
+use crate::voting::ballot::BallotKind;
+use crate::voting::Voting;
+use anyhow::bail;
+use iter_tools::Itertools;
+use std::fs;
+
+/// the check whether a candidate has more than half of the valid first preferences
+#[derive(Debug, PartialEq)]
+pub struct MajorityCheck {
+    /// papers neither disabled nor invalid
+    pub valid: usize,
+    /// first preferences a candidate needs to win outright
+    pub needed: usize,
+    /// (candidate index, first votes) most first votes first
+    pub first_votes: Vec<(usize, usize)>,
+    pub winner: Option<usize>,
+    /// the top two - more when the second place is tied - empty when someone won
+    pub runoff: Vec<usize>,
+}
+
+/// only ranked papers have first preferences - the other kinds are refused
+pub fn check(voting: &Voting) -> anyhow::Result<MajorityCheck> {
+    if voting.ballot_kind != BallotKind::Ranked {
+        bail!(
+            "{} papers have no first preferences - the absolute majority needs ranked papers",
+            voting.ballot_kind
+        )
+    }
+
+    let valid = voting.counted_papers().count();
+    let needed = valid / 2 + 1;
+
+    let first_votes: Vec<(usize, usize)> = voting
+        .candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| (index, candidate.get_first_votes()))
+        .sorted_by(|a, b| b.1.cmp(&a.1))
+        .collect();
+
+    let winner = first_votes
+        .first()
+        .filter(|(_, votes)| valid > 0 && *votes >= needed)
+        .map(|(candidate, _)| *candidate);
+
+    let runoff = match (winner, first_votes.get(1)) {
+        (None, Some((_, second))) if valid > 0 => first_votes
+            .iter()
+            .enumerate()
+            .take_while(|(place, (_, votes))| *place < 2 || votes == second)
+            .map(|(_, (candidate, _))| *candidate)
+            .collect(),
+        _ => vec![],
+    };
+
+    Ok(MajorityCheck {
+        valid,
+        needed,
+        first_votes,
+        winner,
+        runoff,
+    })
+}
+
+/// whether the linked runoff already has papers - a runoff that can not be read counts as started
+pub fn runoff_started(voting: &Voting) -> bool {
+    voting
+        .runoff
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .is_some_and(|content| {
+            Voting::load(content).map_or(true, |runoff| !runoff.papers.is_empty())
+        })
+}
+
+/// writes the runoff between the qualified candidates and links it - a started runoff is kept
+pub fn create_runoff(voting: &mut Voting, check: &MajorityCheck) -> anyhow::Result<()> {
+    if let Some(winner) = check.winner {
+        bail!(
+            "{} has the absolute majority - no runoff needed",
+            voting.candidates[winner].name
+        )
+    }
+
+    if check.runoff.is_empty() {
+        bail!("there are no valid papers yet")
+    }
+
+    if runoff_started(voting) {
+        bail!(
+            "the runoff {} already has papers or can not be read",
+            voting.runoff.clone().unwrap_or_default()
+        )
+    }
+
+    voting.create_runoff(&check.runoff)?.save();
+    voting.save();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::BallotKind;
+    use crate::voting::candidate::Candidate;
+    use crate::voting::majority::{check, create_runoff, runoff_started};
+    use crate::voting::Voting;
+    use std::env::temp_dir;
+    use std::fs;
+
+    fn get_voting(first_votes: &[usize]) -> Voting {
+        let candidates = ["huff", "puff", "muff", "buff"]
+            .iter()
+            .take(first_votes.len())
+            .map(|name| Candidate::new(name.to_string(), 1))
+            .collect();
+
+        let mut voting = Voting::new(candidates, "test.txt", 1).unwrap();
+
+        for (index, votes) in first_votes.iter().enumerate() {
+            for _ in 0..*votes {
+                voting.candidate_selections[0].search_text = voting.candidates[index].name.clone();
+                voting.vote();
+            }
+        }
+
+        voting
+    }
+
+    #[test]
+    fn more_than_half_wins() {
+        let result = check(&get_voting(&[1, 3, 1])).unwrap();
+
+        assert_eq!(result.valid, 5);
+        assert_eq!(result.needed, 3);
+        assert_eq!(result.winner, Some(1));
+        assert!(result.runoff.is_empty());
+    }
+
+    #[test]
+    fn half_is_not_enough() {
+        let result = check(&get_voting(&[2, 2])).unwrap();
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.runoff, vec![0, 1]);
+    }
+
+    #[test]
+    fn tied_second_places_qualify() {
+        let result = check(&get_voting(&[3, 2, 2, 1])).unwrap();
+
+        assert_eq!(result.winner, None);
+        assert_eq!(result.runoff, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn no_papers() {
+        let result = check(&get_voting(&[0, 0])).unwrap();
+
+        assert_eq!(result.winner, None);
+        assert!(result.runoff.is_empty());
+    }
+
+    #[test]
+    fn needs_first_preferences() {
+        let mut voting = get_voting(&[1, 2]);
+        voting.ballot_kind = BallotKind::Score;
+
+        assert!(check(&voting).is_err());
+    }
+
+    #[test]
+    fn runoff_is_replaced_until_it_has_papers() {
+        let mut voting = get_voting(&[2, 2, 1]);
+        voting.save_path = temp_dir()
+            .join("majority-election.json")
+            .to_string_lossy()
+            .to_string();
+
+        let result = check(&voting).unwrap();
+        create_runoff(&mut voting, &result).unwrap();
+
+        let path = voting.runoff.clone().unwrap();
+        assert!(!runoff_started(&voting));
+        assert!(create_runoff(&mut voting, &result).is_ok());
+
+        let mut runoff = Voting::load(fs::read_to_string(&path).unwrap()).unwrap();
+        runoff.candidate_selections[0].search_text = "huff".to_string();
+        runoff.vote();
+        runoff.save();
+
+        assert!(runoff_started(&voting));
+        assert!(create_runoff(&mut voting, &result).is_err());
+        assert_eq!(
+            Voting::load(fs::read_to_string(&path).unwrap())
+                .unwrap()
+                .papers
+                .len(),
+            1
+        );
+    }
+}
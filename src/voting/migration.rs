@@ -0,0 +1,179 @@
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// upgrades saves written by earlier versions
+pub fn upgrade(save: &mut Value) {
+    number_papers(save);
+    add_ids(save);
+}
+
+/// papers entered before they had serial numbers are numbered in the order they were entered
+fn number_papers(save: &mut Value) {
+    if let Some(papers) = save.get_mut("papers").and_then(Value::as_array_mut) {
+        for (index, paper) in papers.iter_mut().enumerate() {
+            if let Some(paper) = paper.as_object_mut() {
+                paper.entry("serial").or_insert((index + 1).into());
+            }
+        }
+    }
+}
+
+/// saves written before candidates had ids named the candidates in papers and selections,
+/// and the oldest ranked papers stored invalid slots as terminal styled text -
+/// names without a candidate become invalid
+fn add_ids(save: &mut Value) {
+    let Some(candidates) = save.get_mut("candidates").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    if candidates
+        .iter()
+        .all(|candidate| candidate.get("id").is_some())
+    {
+        return;
+    }
+
+    let mut ids = BTreeMap::new();
+
+    for (id, candidate) in candidates.iter_mut().enumerate() {
+        if let Some(candidate) = candidate.as_object_mut() {
+            candidate.insert("id".to_string(), id.into());
+
+            if let Some(name) = candidate.get("name").and_then(Value::as_str) {
+                ids.insert(name.to_string(), id);
+            }
+        }
+    }
+
+    if let Some(papers) = save.get_mut("papers").and_then(Value::as_array_mut) {
+        for paper in papers {
+            if let Some(vote) = paper.get_mut("voting") {
+                upgrade_vote(vote, &ids);
+            }
+        }
+    }
+
+    if let Some(approved) = save.get_mut("approval_selection") {
+        upgrade_names(approved, &ids);
+    }
+
+    for selection in ["score_selection", "grade_selection", "point_selection"] {
+        if let Some(marks) = save.get_mut(selection).and_then(Value::as_object_mut) {
+            upgrade_keys(marks, &ids);
+        }
+    }
+}
+
+fn upgrade_vote(vote: &mut Value, ids: &BTreeMap<String, usize>) {
+    match vote {
+        Value::Array(ranking) => {
+            for preference in ranking {
+                *preference = upgrade_preference(preference, ids);
+            }
+        }
+        Value::Object(marks) => {
+            if let Some(approved) = marks.get_mut("approved") {
+                upgrade_names(approved, ids);
+            }
+
+            for kind in ["scores", "grades", "points"] {
+                if let Some(marks) = marks.get_mut(kind).and_then(Value::as_object_mut) {
+                    upgrade_keys(marks, ids);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn upgrade_preference(preference: &Value, ids: &BTreeMap<String, usize>) -> Value {
+    let name = match preference {
+        Value::String(name) => name.as_str(),
+        Value::Object(typed) if typed.get("kind") == Some(&json!("candidate")) => typed
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default(),
+        _ => return preference.clone(),
+    };
+
+    match ids.get(name) {
+        Some(id) => json!({"kind": "candidate", "id": id}),
+        None => json!({"kind": "invalid"}),
+    }
+}
+
+/// a list of names becomes a list of ids
+fn upgrade_names(names: &mut Value, ids: &BTreeMap<String, usize>) {
+    if let Some(names) = names.as_array_mut() {
+        *names = names
+            .iter()
+            .filter_map(|name| ids.get(name.as_str()?))
+            .map(|id| json!(id))
+            .collect();
+    }
+}
+
+/// marks keyed by name become marks keyed by id
+fn upgrade_keys(marks: &mut Map<String, Value>, ids: &BTreeMap<String, usize>) {
+    *marks = marks
+        .iter()
+        .filter_map(|(name, mark)| Some((ids.get(name)?.to_string(), mark.clone())))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::ballot::{Grade, Preference};
+    use crate::voting::candidate::Candidate;
+    use crate::voting::Voting;
+
+    #[test]
+    fn upgrades_named_papers() {
+        let save = r#"{
+            "candidate_selections": [],
+            "approval_selection": ["puff"],
+            "grade_selection": {"huff": "Good", "gone": "Poor"},
+            "candidates": [
+                {"name": "huff", "votes": [1, 0]},
+                {"name": "puff", "votes": [0, 1]}
+            ],
+            "papers": [
+                {"voting": ["huff", "\u001b[37m\u001b[2minvalid\u001b[0m"], "disabled": false, "invalid": false},
+                {"voting": [{"kind": "candidate", "name": "puff"}, {"kind": "blank"}], "disabled": false, "invalid": false},
+                {"voting": {"approved": ["puff", "gone"]}, "disabled": false, "invalid": false},
+                {"voting": {"grades": {"puff": "VeryGood"}}, "disabled": false, "invalid": false}
+            ],
+            "invalid_vote_count": 0,
+            "allowed_votes": 2,
+            "save_path": "test.json"
+        }"#;
+
+        let voting = Voting::load(save.to_string()).unwrap();
+
+        assert_eq!(voting.candidates[1].id, 1);
+        assert_eq!(voting.papers[3].serial, 4);
+        assert_eq!(
+            voting.papers[0].voting.ranking(),
+            [Preference::Candidate(0), Preference::Invalid]
+        );
+        assert_eq!(
+            voting.papers[1].voting.ranking(),
+            [Preference::Candidate(1), Preference::Blank]
+        );
+        assert_eq!(voting.papers[2].voting.approved(), [1]);
+        assert_eq!(voting.papers[3].voting.grades(), [(1, Grade::VeryGood)]);
+        assert!(voting.approval_selection.contains(&1));
+        assert_eq!(voting.grade_selection.len(), 1);
+    }
+
+    #[test]
+    fn keeps_current_saves() {
+        let mut voting =
+            Voting::new(vec![Candidate::new("huff".to_string(), 1)], "test.json", 1).unwrap();
+        voting.approval_selection.insert(3);
+
+        let content = serde_json::to_string(&voting).unwrap();
+
+        assert_eq!(Voting::load(content).unwrap(), voting);
+    }
+}
@@ -1,9 +1,15 @@
-use crate::voting::ballot::BallotPaper;
+use crate::voting::ballot::{BallotKind, BallotPaper, Grade, Preference, Reason, Vote, MAX_SCORE};
 use crate::voting::candidate::Candidate;
-use crate::voting::candidate_selection::CandidateSelection;
+use crate::voting::candidate_selection::{marks, CandidateSelection};
+use crate::voting::counting::{CountResult, Method};
+use crate::voting::tie_break::{TieBreak, TieBreaker};
+use crate::voting::validity::{Decision, Validity};
+use crate::voting::weighting::Weighting;
 use anyhow::bail;
-use console::style;
+use iter_tools::Itertools;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 use std::string::ToString;
@@ -14,24 +20,78 @@ pub mod ballot;
 
 pub mod candidate_selection;
 
-static SELECTION_HEADER: &'static [&'static str] = &["First", "Second", "Third", "Fourth"];
+pub mod counting;
 
-#[derive(Deserialize, Serialize, Eq, PartialEq, Debug)]
+pub mod majority;
+
+pub mod migration;
+
+pub mod tie_break;
+
+pub mod validity;
+
+pub mod weighting;
+
+static SELECTION_HEADER: &[&str] = &["First", "Second", "Third", "Fourth"];
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
 pub struct Voting {
     pub candidate_selections: Vec<CandidateSelection>,
+    #[serde(default)]
+    pub approval_selection: BTreeSet<usize>,
+    #[serde(default)]
+    pub score_selection: BTreeMap<usize, u8>,
+    #[serde(default)]
+    pub grade_selection: BTreeMap<usize, Grade>,
+    #[serde(default)]
+    pub point_selection: BTreeMap<usize, usize>,
+    /// the paper loaded into the selections - the next vote replaces it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editing: Option<usize>,
+    /// the box or batch new papers are taken from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<String>,
 
     pub candidates: Vec<Candidate>,
     pub papers: Vec<BallotPaper>,
     invalid_vote_count: usize,
+    #[serde(default)]
+    blank_vote_count: usize,
 
     pub allowed_votes: usize,
+    #[serde(default)]
+    pub ballot_kind: BallotKind,
+    #[serde(default = "default_seats")]
+    pub seats: usize,
+    #[serde(default)]
+    pub weighting: Weighting,
+    #[serde(default)]
+    pub method: Method,
+    #[serde(default)]
+    pub tie_break: TieBreak,
+    /// which slots of a ranked paper count when some are faulty
+    #[serde(default)]
+    pub validity: Validity,
+    /// points or credits every voter can spend on cumulative and quadratic papers
+    #[serde(default = "default_budget")]
+    pub budget: usize,
+    /// a winner needs more than half of the valid first preferences
+    #[serde(default)]
+    pub absolute_majority: bool,
+    /// the save of the round this runoff follows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_round: Option<String>,
+    /// the save of the runoff created for this round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runoff: Option<String>,
 
     save_path: String,
 }
 
 impl Voting {
+    /// numbers the candidates in the given order - their ids never change afterwards
     pub fn new<P: AsRef<Path>>(
-        candidates: Vec<Candidate>,
+        mut candidates: Vec<Candidate>,
         save_path: P,
         allowed_votes: usize,
     ) -> anyhow::Result<Voting> {
@@ -41,16 +101,37 @@ impl Voting {
 
         let mut candidate_selections = vec![];
 
-        for index in 0..allowed_votes {
-            candidate_selections.push(CandidateSelection::new(SELECTION_HEADER[index].to_string()))
+        for header in SELECTION_HEADER.iter().take(allowed_votes) {
+            candidate_selections.push(CandidateSelection::new(header.to_string()))
+        }
+
+        for (id, candidate) in candidates.iter_mut().enumerate() {
+            candidate.id = id;
         }
 
         Ok(Voting {
             candidate_selections,
+            approval_selection: BTreeSet::new(),
+            score_selection: BTreeMap::new(),
+            grade_selection: BTreeMap::new(),
+            point_selection: BTreeMap::new(),
+            editing: None,
+            batch: None,
             candidates,
             papers: vec![],
             invalid_vote_count: 0,
+            blank_vote_count: 0,
             allowed_votes,
+            ballot_kind: BallotKind::default(),
+            seats: default_seats(),
+            weighting: Weighting::default(),
+            method: Method::default(),
+            tie_break: TieBreak::default(),
+            validity: Validity::default(),
+            budget: default_budget(),
+            absolute_majority: false,
+            first_round: None,
+            runoff: None,
             save_path: save_path.as_ref().to_str().unwrap().to_string(),
         })
     }
@@ -60,93 +141,652 @@ impl Voting {
         fs::write(&self.save_path, content).unwrap();
     }
 
+    /// reads a save - saves written before candidate ids existed are upgraded
     pub fn load(content: String) -> serde_json::Result<Voting> {
-        serde_json::from_str(&content)
+        let mut save: Value = serde_json::from_str(&content)?;
+        migration::upgrade(&mut save);
+
+        serde_json::from_value(save)
+    }
+
+    /// changes the displayed name - papers keep pointing at the candidate
+    pub fn rename(&mut self, name: &str, new_name: &str) -> anyhow::Result<()> {
+        let new_name = new_name.trim();
+
+        if new_name.is_empty() {
+            bail!("the new name is empty")
+        }
+
+        if self.candidate_index(new_name).is_some() {
+            bail!("there already is a candidate called {}", new_name)
+        }
+
+        match self.candidate_index(name) {
+            Some(index) => {
+                self.candidates[index].name = new_name.to_string();
+                Ok(())
+            }
+            None => bail!("there is no candidate called {}", name),
+        }
+    }
+
+    /// a new election between the given candidates saved next to this one - both are linked
+    pub fn create_runoff(&mut self, candidates: &[usize]) -> anyhow::Result<Voting> {
+        let path = Path::new(&self.save_path);
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("save");
+        let file_name = match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) => format!("{}-runoff.{}", stem, extension),
+            None => format!("{}-runoff", stem),
+        };
+
+        let allowed_votes = match self.ballot_kind {
+            BallotKind::Ranked => 1,
+            _ => self.allowed_votes,
+        };
+
+        let candidates = candidates
+            .iter()
+            .map(|index| {
+                let mut candidate =
+                    Candidate::new(self.candidates[*index].name.to_string(), allowed_votes);
+                candidate.party = self.candidates[*index].party.clone();
+                candidate
+            })
+            .collect();
+
+        let mut runoff = Voting::new(candidates, path.with_file_name(file_name), allowed_votes)?;
+        runoff.ballot_kind = self.ballot_kind;
+        runoff.method = Method::default_for(self.ballot_kind);
+        runoff.budget = self.budget;
+        runoff.tie_break = self.tie_break;
+        runoff.first_round = Some(self.save_path.to_string());
+
+        self.runoff = Some(runoff.save_path.to_string());
+
+        Ok(runoff)
     }
 
     pub fn clear_selections(&mut self) {
         for selection in &mut self.candidate_selections {
             selection.clear()
         }
+
+        self.approval_selection.clear();
+        self.score_selection.clear();
+        self.grade_selection.clear();
+        self.point_selection.clear();
     }
 
+    /// the budget the current paper uses
+    pub fn spent(&self) -> usize {
+        self.point_selection
+            .values()
+            .map(|votes| self.ballot_kind.cost(*votes))
+            .sum()
+    }
+
+    pub fn remaining_budget(&self) -> usize {
+        self.budget.saturating_sub(self.spent())
+    }
+
+    /// sets the votes for the option on the current paper - `None` removes them
+    ///
+    /// returns false and keeps the paper unchanged when the budget would be exceeded
+    pub fn set_points(&mut self, id: usize, votes: Option<usize>) -> bool {
+        let current = self.point_selection.get(&id).copied().unwrap_or(0);
+        let votes = votes.unwrap_or(0);
+
+        if self.spent() - self.ballot_kind.cost(current) + self.ballot_kind.cost(votes)
+            > self.budget
+        {
+            return false;
+        }
+
+        if votes == 0 {
+            self.point_selection.remove(&id);
+        } else {
+            self.point_selection.insert(id, votes);
+        }
+
+        true
+    }
+
+    /// sets the grade of the candidate on the current paper - `None` removes it
+    pub fn set_grade(&mut self, id: usize, grade: Option<Grade>) {
+        match grade {
+            Some(grade) => {
+                self.grade_selection.insert(id, grade);
+            }
+            None => {
+                self.grade_selection.remove(&id);
+            }
+        }
+    }
+
+    /// sets the stars of the candidate on the current paper - `None` removes them
+    pub fn set_score(&mut self, id: usize, score: Option<u8>) {
+        match score {
+            Some(score) => {
+                self.score_selection.insert(id, score.min(MAX_SCORE));
+            }
+            None => {
+                self.score_selection.remove(&id);
+            }
+        }
+    }
+
+    /// switches the approval of the candidate on the current paper
+    pub fn toggle_approval(&mut self, id: usize) {
+        if !self.approval_selection.remove(&id) {
+            self.approval_selection.insert(id);
+        }
+    }
+
+    /// records the current selections as a new paper or as the correction of the edited one
     pub fn vote(&mut self) {
-        let votes: Vec<(bool, String)> = self
+        let (vote, is_valid) = match self.ballot_kind {
+            BallotKind::Ranked => self.ranked_vote(),
+            BallotKind::Approval => self.approval_vote(),
+            BallotKind::Score => self.score_vote(),
+            BallotKind::Grades => self.grade_vote(),
+            BallotKind::Cumulative | BallotKind::Quadratic => self.point_vote(),
+        };
+
+        match self.editing.take() {
+            Some(index) if index < self.papers.len() => {
+                let counted = !self.papers[index].disabled;
+
+                if counted {
+                    self.tally(index, false);
+                }
+
+                self.papers[index].correct(vote, !is_valid);
+
+                if counted {
+                    self.tally(index, true);
+                }
+            }
+            _ => {
+                let mut paper = BallotPaper::new(vote, !is_valid);
+                paper.serial = self
+                    .papers
+                    .iter()
+                    .map(|paper| paper.serial)
+                    .max()
+                    .unwrap_or(0)
+                    + 1;
+                paper.batch = self.batch.clone();
+
+                self.papers.push(paper);
+                self.tally(self.papers.len() - 1, true);
+            }
+        }
+
+        self.clear_selections();
+    }
+
+    /// records a paper without any marks, whatever was selected - refused while a paper is
+    /// corrected so that it is not blanked by accident
+    pub fn vote_blank(&mut self) -> bool {
+        if self.editing.is_some() {
+            return false;
+        }
+
+        self.clear_selections();
+        self.vote();
+
+        true
+    }
+
+    /// loads the paper into the selections to be corrected - disabled papers can not be edited,
+    /// invalid slots stay invalid unless they are overwritten
+    pub fn edit_vote(&mut self, index: usize) -> bool {
+        let vote = match self.papers.get(index) {
+            Some(paper) if !paper.disabled => paper.voting.clone(),
+            _ => return false,
+        };
+
+        self.clear_selections();
+
+        match vote {
+            Vote::Ranked(ranking) => {
+                for (selection, preference) in self.candidate_selections.iter_mut().zip(ranking) {
+                    if preference == Preference::Invalid {
+                        selection.mark_invalid();
+                    } else if let Some(candidate) = preference
+                        .candidate()
+                        .and_then(|id| self.candidates.iter().find(|candidate| candidate.id == id))
+                    {
+                        selection.select(&candidate.name, &self.candidates);
+                    }
+                }
+            }
+            Vote::Approval { approved } => self.approval_selection = approved,
+            Vote::Score { scores } => self.score_selection = scores,
+            Vote::Grades { grades } => self.grade_selection = grades,
+            Vote::Points { points } => self.point_selection = points,
+        }
+
+        self.editing = Some(index);
+
+        true
+    }
+
+    /// drops the loaded paper without changing it
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+        self.clear_selections();
+    }
+
+    fn approval_vote(&self) -> (Vote, bool) {
+        let approved: BTreeSet<usize> = self
+            .approval_selection
+            .iter()
+            .filter(|id| self.candidate_position(**id).is_some())
+            .copied()
+            .collect();
+
+        let is_valid = !approved.is_empty();
+
+        (Vote::Approval { approved }, is_valid)
+    }
+
+    fn score_vote(&self) -> (Vote, bool) {
+        let scores: BTreeMap<usize, u8> = self
+            .score_selection
+            .iter()
+            .filter(|(id, _)| self.candidate_position(**id).is_some())
+            .map(|(id, score)| (*id, *score))
+            .collect();
+
+        let is_valid = !scores.is_empty();
+
+        (Vote::Score { scores }, is_valid)
+    }
+
+    fn grade_vote(&self) -> (Vote, bool) {
+        let grades: BTreeMap<usize, Grade> = self
+            .grade_selection
+            .iter()
+            .filter(|(id, _)| self.candidate_position(**id).is_some())
+            .map(|(id, grade)| (*id, *grade))
+            .collect();
+
+        let is_valid = !grades.is_empty();
+
+        (Vote::Grades { grades }, is_valid)
+    }
+
+    fn point_vote(&self) -> (Vote, bool) {
+        let points: BTreeMap<usize, usize> = self
+            .point_selection
+            .iter()
+            .filter(|(id, votes)| self.candidate_position(**id).is_some() && **votes > 0)
+            .map(|(id, votes)| (*id, *votes))
+            .collect();
+
+        let is_valid = !points.is_empty() && self.spent() <= self.budget;
+
+        (Vote::Points { points }, is_valid)
+    }
+
+    /// how the validity rule judges the ranked slots entered so far
+    pub fn decision(&self) -> Decision {
+        self.validity
+            .decide(&marks(&self.candidate_selections, &self.candidates))
+    }
+
+    /// slots the validity rule does not count become invalid - void papers keep what was entered
+    fn ranked_vote(&self) -> (Vote, bool) {
+        let decision = self.decision();
+
+        let ranking = self
             .candidate_selections
             .iter()
-            .enumerate()
-            .map(|(index, selection)| {
-                (
-                    selection.is_valid(&self.candidate_selections, &self.candidates, index),
-                    selection,
-                )
-            })
-            .map(|(valid, selection)| {
-                if !valid {
-                    return (valid, style("invalid").white().dim().to_string());
+            .zip(&decision.counted)
+            .map(|(selection, counted)| {
+                if selection.search_text.is_empty() {
+                    return Preference::Blank;
                 }
 
-                (
-                    valid,
-                    selection
-                        .selected_candidate(&self.candidates)
-                        .unwrap_or(style("invalid").white().dim().to_string()),
-                )
+                match selection
+                    .selected_candidate(&self.candidates)
+                    .and_then(|name| self.candidate_index(&name))
+                {
+                    Some(index) if *counted || !decision.is_valid() => {
+                        Preference::Candidate(self.candidates[index].id)
+                    }
+                    _ => Preference::Invalid,
+                }
             })
             .collect();
 
-        let is_valid = votes.iter().any(|(valid, _)| *valid);
+        (Vote::Ranked(ranking), decision.is_valid())
+    }
 
-        self.papers.push(BallotPaper::new(
-            votes.iter().map(|(_, text)| text.to_string()).collect(),
-            !is_valid,
-        ));
+    /// takes the paper out of the tallies - does nothing if it already is disabled
+    pub fn disable_vote(&mut self, index: usize) {
+        match self.papers.get_mut(index) {
+            Some(paper) if !paper.disabled => paper.disabled = true,
+            _ => return,
+        }
 
-        if is_valid {
-            'outer: for (index, (_, vote)) in votes.iter().enumerate() {
-                for candidate in &mut self.candidates {
-                    if candidate.name == *vote {
-                        candidate.vote(index);
-                        continue 'outer;
-                    }
+        self.tally(index, false);
+    }
+
+    /// puts a disabled paper back into the tallies - does nothing if it is counted
+    pub fn enable_vote(&mut self, index: usize) {
+        match self.papers.get_mut(index) {
+            Some(paper) if paper.disabled => {
+                paper.disabled = false;
+
+                if !paper.invalid {
+                    paper.reason = None;
                 }
             }
-        } else {
-            self.invalid_vote_count += 1;
+            _ => return,
         }
 
-        self.clear_selections();
+        self.tally(index, true);
     }
 
-    pub fn disable_vote(&mut self, index: usize) {
-        self.papers[index].disabled = true;
+    /// records why the paper was rejected - only invalid or disabled papers take a reason
+    pub fn set_reason(&mut self, index: usize, reason: Option<Reason>) -> bool {
+        match self.papers.get_mut(index) {
+            Some(paper) if paper.is_rejected() => {
+                paper.reason = reason;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// the number of invalid or disabled papers per reason - `None` when no reason was given
+    pub fn rejections(&self) -> BTreeMap<Option<Reason>, usize> {
+        let mut rejections = BTreeMap::new();
+
+        for paper in self.papers.iter().filter(|paper| paper.is_rejected()) {
+            *rejections.entry(paper.reason.clone()).or_insert(0) += 1;
+        }
 
+        rejections
+    }
+
+    /// adds or removes the marks of the paper to the candidates, the invalid or the blank count
+    fn tally(&mut self, index: usize, add: bool) {
         let paper = &self.papers[index];
 
-        if paper.invalid {
-            self.invalid_vote_count -= 1;
+        let count = if paper.invalid {
+            Some(&mut self.invalid_vote_count)
+        } else if paper.blank {
+            Some(&mut self.blank_vote_count)
         } else {
-            for (index, vote) in paper.voting.iter().enumerate() {
-                if let Some(candidate) = self
-                    .candidates
-                    .iter_mut()
-                    .find(|candidate| &candidate.name == vote)
-                {
-                    candidate.unvote(index)
+            None
+        };
+
+        if let Some(count) = count {
+            if add {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+
+            return;
+        }
+
+        let levels: Vec<(usize, usize)> = paper
+            .voting
+            .approved()
+            .into_iter()
+            .map(|id| (id, 0))
+            .chain(
+                paper
+                    .voting
+                    .ranking()
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(level, vote)| Some((vote.candidate()?, level))),
+            )
+            .collect();
+
+        for (id, level) in levels {
+            if let Some(candidate) = self
+                .candidates
+                .iter_mut()
+                .find(|candidate| candidate.id == id)
+            {
+                if add {
+                    candidate.vote(level)
+                } else {
+                    candidate.unvote(level)
                 }
             }
         }
     }
 
+    /// the batches in the order their first paper was entered - `None` for papers without one
+    pub fn batches(&self) -> Vec<Option<String>> {
+        self.papers
+            .iter()
+            .map(|paper| paper.batch.clone())
+            .unique()
+            .collect()
+    }
+
+    /// the election as if only the papers of the batch had been entered
+    pub fn batch_voting(&self, batch: &Option<String>) -> Voting {
+        let mut voting = self.clone();
+
+        voting.papers.clear();
+        voting.invalid_vote_count = 0;
+        voting.blank_vote_count = 0;
+        voting.editing = None;
+
+        for candidate in &mut voting.candidates {
+            candidate.votes.fill(0);
+        }
+
+        for paper in self.papers.iter().filter(|paper| paper.batch == *batch) {
+            voting.papers.push(paper.clone());
+
+            if !paper.disabled {
+                voting.tally(voting.papers.len() - 1, true);
+            }
+        }
+
+        voting
+    }
+
     pub fn invalid(&self) -> usize {
         self.invalid_vote_count
     }
+
+    pub fn blank(&self) -> usize {
+        self.blank_vote_count
+    }
+
+    /// papers that are neither disabled, invalid nor blank
+    pub fn counted_papers(&self) -> impl Iterator<Item = &BallotPaper> {
+        self.papers
+            .iter()
+            .filter(|paper| !paper.disabled && !paper.invalid && !paper.blank)
+    }
+
+    pub fn candidate_names(&self) -> Vec<String> {
+        self.candidates
+            .iter()
+            .map(|candidate| candidate.name.to_string())
+            .collect()
+    }
+
+    /// the position of the candidate called `name` - only used to resolve what was typed
+    pub fn candidate_index(&self, name: &str) -> Option<usize> {
+        self.candidates
+            .iter()
+            .position(|candidate| candidate.name == name)
+    }
+
+    /// the position of the candidate with the id
+    pub fn candidate_position(&self, id: usize) -> Option<usize> {
+        self.candidates
+            .iter()
+            .position(|candidate| candidate.id == id)
+    }
+
+    /// the name of the candidate with the id
+    pub fn candidate_name(&self, id: usize) -> &str {
+        self.candidate_position(id)
+            .map_or("unknown", |index| &self.candidates[index].name)
+    }
+
+    /// the candidate indices of every counted paper, best first
+    pub fn rankings(&self) -> Vec<Vec<usize>> {
+        self.counted_papers()
+            .map(|paper| {
+                paper
+                    .voting
+                    .ranking()
+                    .iter()
+                    .filter_map(|vote| self.candidate_position(vote.candidate()?))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// counts the papers with the configured method - ties left to the chair stay
+    pub fn count(&self) -> CountResult {
+        self.method
+            .counter()
+            .count(self, &mut TieBreaker::new(self))
+    }
+
+    /// how many counted papers approve of every candidate
+    pub fn approvals(&self) -> Vec<usize> {
+        let mut approvals = vec![0; self.candidates.len()];
+
+        for paper in self.counted_papers() {
+            for id in paper.voting.approved() {
+                if let Some(candidate) = self.candidate_position(id) {
+                    approvals[candidate] += 1;
+                }
+            }
+        }
+
+        approvals
+    }
+
+    /// the stars of every counted paper by candidate index - unscored candidates get 0
+    pub fn star_ballots(&self) -> Vec<Vec<u8>> {
+        self.counted_papers()
+            .filter(|paper| matches!(paper.voting, Vote::Score { .. }))
+            .map(|paper| {
+                let mut stars = vec![0; self.candidates.len()];
+
+                for (id, score) in paper.voting.scores() {
+                    if let Some(candidate) = self.candidate_position(id) {
+                        stars[candidate] = score;
+                    }
+                }
+
+                stars
+            })
+            .collect()
+    }
+
+    /// the grades of every counted paper by candidate index - ungraded candidates are rejected
+    pub fn grade_ballots(&self) -> Vec<Vec<Grade>> {
+        self.counted_papers()
+            .filter(|paper| matches!(paper.voting, Vote::Grades { .. }))
+            .map(|paper| {
+                let mut grades = vec![Grade::Reject; self.candidates.len()];
+
+                for (id, grade) in paper.voting.grades() {
+                    if let Some(candidate) = self.candidate_position(id) {
+                        grades[candidate] = grade;
+                    }
+                }
+
+                grades
+            })
+            .collect()
+    }
+
+    /// the votes every option got on counted budget papers
+    pub fn point_totals(&self) -> Vec<usize> {
+        let mut totals = vec![0; self.candidates.len()];
+
+        for paper in self.counted_papers() {
+            for (id, votes) in paper.voting.points() {
+                if let Some(candidate) = self.candidate_position(id) {
+                    totals[candidate] += votes;
+                }
+            }
+        }
+
+        totals
+    }
+
+    /// the points of every candidate under the configured weighting - approvals and stars count 1
+    pub fn scores(&self) -> Vec<f64> {
+        let mut scores: Vec<f64> = self
+            .approvals()
+            .iter()
+            .zip(self.point_totals())
+            .map(|(approvals, points)| (approvals + points) as f64)
+            .collect();
+
+        for stars in self.star_ballots() {
+            for (candidate, stars) in stars.iter().enumerate() {
+                scores[candidate] += *stars as f64;
+            }
+        }
+
+        for paper in self.counted_papers() {
+            // (slot, candidate) - the slot keeps the rank the voter marked
+            let positions: Vec<(usize, usize)> = paper
+                .voting
+                .ranking()
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, vote)| {
+                    Some((slot, self.candidate_position(vote.candidate()?)?))
+                })
+                .collect();
+
+            for (filled_rank, (slot, candidate)) in positions.iter().enumerate() {
+                // modified borda only counts the filled preferences
+                let position = match self.weighting {
+                    Weighting::ModifiedBorda => filled_rank,
+                    _ => *slot,
+                };
+
+                scores[*candidate] +=
+                    self.weighting
+                        .points(position, positions.len(), self.allowed_votes);
+            }
+        }
+
+        scores
+    }
+}
+
+fn default_seats() -> usize {
+    1
+}
+
+fn default_budget() -> usize {
+    10
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::voting::ballot::BallotPaper;
+    use crate::voting::ballot::{BallotKind, BallotPaper, Grade, Preference, Reason, Vote};
     use crate::voting::candidate::Candidate;
+    use crate::voting::validity::Validity;
+    use crate::voting::weighting::Weighting;
     use crate::voting::Voting;
     use std::env::temp_dir;
     use std::fs;
@@ -164,9 +804,20 @@ mod tests {
         let voting = Voting::new(Vec::from(get_candidates(4)), "test.txt", 4).unwrap();
 
         assert_eq!(voting.allowed_votes, 4);
+        assert_eq!(voting.seats, 1);
         assert_eq!(voting.invalid_vote_count, 0);
         assert_eq!(voting.save_path, "test.txt");
-        assert_eq!(voting.candidates, Vec::from(get_candidates(4)));
+        assert_eq!(
+            voting.candidate_names(),
+            ["time test", "test", "ok i think"]
+        );
+
+        let ids: Vec<usize> = voting
+            .candidates
+            .iter()
+            .map(|candidate| candidate.id)
+            .collect();
+        assert_eq!(ids, vec![0, 1, 2]);
     }
 
     #[test]
@@ -219,6 +870,189 @@ mod tests {
         assert_eq!(voting.papers.len(), 1);
     }
 
+    #[test]
+    fn vote_approval() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+        voting.ballot_kind = BallotKind::Approval;
+
+        voting.toggle_approval(1);
+        voting.toggle_approval(2);
+        voting.toggle_approval(0);
+        voting.toggle_approval(1);
+        voting.vote();
+
+        assert!(voting.approval_selection.is_empty());
+
+        voting.toggle_approval(2);
+        voting.vote();
+
+        voting.vote();
+
+        assert_eq!(voting.approvals(), vec![1, 0, 2]);
+        assert_eq!(voting.scores(), vec![1.0, 0.0, 2.0]);
+        assert_eq!(voting.invalid(), 0);
+        assert_eq!(voting.blank(), 1);
+
+        voting.disable_vote(0);
+
+        assert_eq!(voting.approvals(), vec![0, 0, 1]);
+        assert_eq!(voting.candidates[2].get_first_votes(), 1);
+    }
+
+    #[test]
+    fn vote_score() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+        voting.ballot_kind = BallotKind::Score;
+
+        voting.set_score(1, Some(4));
+        voting.set_score(2, Some(9));
+        voting.vote();
+
+        assert!(voting.score_selection.is_empty());
+
+        voting.set_score(1, Some(2));
+        voting.set_score(1, None);
+        voting.vote();
+
+        voting.set_score(0, Some(1));
+        voting.vote();
+
+        assert_eq!(voting.star_ballots(), vec![vec![0, 4, 5], vec![1, 0, 0]]);
+        assert_eq!(voting.scores(), vec![1.0, 4.0, 5.0]);
+        assert_eq!(voting.invalid(), 0);
+        assert_eq!(voting.blank(), 1);
+    }
+
+    #[test]
+    fn vote_grades() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+        voting.ballot_kind = BallotKind::Grades;
+
+        voting.set_grade(1, Some(Grade::Good));
+        voting.set_grade(2, Some(Grade::Poor));
+        voting.set_grade(2, None);
+        voting.vote();
+
+        assert!(voting.grade_selection.is_empty());
+
+        voting.vote();
+
+        assert_eq!(
+            voting.grade_ballots(),
+            vec![vec![Grade::Reject, Grade::Good, Grade::Reject]]
+        );
+        assert_eq!(voting.invalid(), 0);
+        assert_eq!(voting.blank(), 1);
+    }
+
+    #[test]
+    fn refuses_over_budget() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+        voting.ballot_kind = BallotKind::Quadratic;
+        voting.budget = 10;
+
+        assert!(voting.set_points(1, Some(3)));
+        assert!(!voting.set_points(2, Some(2)));
+        assert!(voting.set_points(2, Some(1)));
+        assert_eq!(voting.remaining_budget(), 0);
+
+        assert!(voting.set_points(1, Some(2)));
+        assert!(voting.set_points(2, Some(2)));
+        assert_eq!(voting.remaining_budget(), 2);
+        voting.vote();
+
+        voting.ballot_kind = BallotKind::Cumulative;
+        voting.set_points(1, Some(10));
+        voting.vote();
+
+        voting.vote();
+
+        assert_eq!(voting.point_totals(), vec![0, 12, 2]);
+        assert_eq!(voting.invalid(), 0);
+        assert_eq!(voting.blank(), 1);
+    }
+
+    #[test]
+    fn create_runoff() {
+        let mut candidates = Vec::from(get_candidates(2));
+        candidates[2].party = Some("huff".to_string());
+
+        let mut voting = Voting::new(candidates, "elections/board.json", 2).unwrap();
+        voting.absolute_majority = true;
+
+        let runoff = voting.create_runoff(&[2, 0]).unwrap();
+
+        assert_eq!(runoff.candidate_names(), vec!["ok i think", "time test"]);
+        assert_eq!(runoff.candidates[0].party, Some("huff".to_string()));
+        assert_eq!(runoff.candidates[0].votes, vec![0]);
+        assert_eq!(runoff.allowed_votes, 1);
+        assert!(!runoff.absolute_majority);
+        assert_eq!(runoff.save_path, "elections/board-runoff.json");
+        assert_eq!(runoff.first_round, Some("elections/board.json".to_string()));
+        assert_eq!(
+            voting.runoff,
+            Some("elections/board-runoff.json".to_string())
+        );
+    }
+
+    #[test]
+    fn rankings() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "ok".to_string();
+        voting.candidate_selections[1].search_text = "ti".to_string();
+        voting.vote();
+
+        voting.candidate_selections[0].search_text = "te".to_string();
+        voting.candidate_selections[1].search_text = "none".to_string();
+        voting.vote();
+
+        voting.candidate_selections[0].search_text = "te".to_string();
+        voting.vote();
+        voting.disable_vote(2);
+
+        assert_eq!(voting.rankings(), vec![vec![2, 0], vec![1]]);
+    }
+
+    #[test]
+    fn scores() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "ok".to_string();
+        voting.candidate_selections[1].search_text = "ti".to_string();
+        voting.vote();
+
+        voting.candidate_selections[0].search_text = "te".to_string();
+        voting.vote();
+
+        assert_eq!(voting.scores(), vec![1.0, 2.0, 2.0]);
+
+        voting.weighting = Weighting::ModifiedBorda;
+        assert_eq!(voting.scores(), vec![1.0, 1.0, 2.0]);
+
+        voting.weighting = Weighting::Plurality;
+        assert_eq!(voting.scores(), vec![0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn scores_skip_empty_slots() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+        voting.weighting = Weighting::ModifiedBorda;
+
+        voting.papers.push(BallotPaper::new(
+            Vote::Ranked(vec![Preference::Invalid, Preference::Candidate(2)]),
+            false,
+        ));
+
+        assert_eq!(voting.scores(), vec![0.0, 0.0, 1.0]);
+
+        voting.weighting = Weighting::Borda;
+        assert_eq!(voting.scores(), vec![0.0, 0.0, 1.0]);
+
+        voting.weighting = Weighting::Plurality;
+        assert_eq!(voting.scores(), vec![0.0, 0.0, 0.0]);
+    }
+
     #[test]
     fn unvote() {
         let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
@@ -241,4 +1075,274 @@ mod tests {
             0
         );
     }
+
+    #[test]
+    fn enable_vote() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.candidate_selections[1].search_text = "ok".to_string();
+        voting.vote();
+        voting.vote();
+
+        voting.disable_vote(0);
+        voting.disable_vote(0);
+        voting.disable_vote(1);
+
+        assert_eq!(voting.candidates[1].votes, vec![0, 0]);
+        assert_eq!(voting.invalid(), 0);
+
+        voting.enable_vote(0);
+        voting.enable_vote(0);
+        voting.enable_vote(1);
+        voting.enable_vote(5);
+
+        assert_eq!(voting.candidates[1].votes, vec![1, 0]);
+        assert_eq!(voting.candidates[2].votes, vec![0, 1]);
+        assert_eq!(voting.blank(), 1);
+        assert_eq!(voting.counted_papers().count(), 1);
+    }
+
+    #[test]
+    fn edit_vote() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "time".to_string();
+        voting.candidate_selections[1].search_text = "ok".to_string();
+        voting.vote();
+
+        assert!(voting.edit_vote(0));
+        assert_eq!(voting.candidate_selections[1].search_text, "ok i think");
+
+        voting.candidate_selections[0].select("test", &voting.candidates);
+        voting.vote();
+
+        assert_eq!(voting.papers.len(), 1);
+        assert_eq!(voting.papers[0].history.len(), 1);
+        assert_eq!(voting.editing, None);
+        assert_eq!(voting.candidates[0].votes, vec![0, 0]);
+        assert_eq!(voting.candidates[1].votes, vec![1, 0]);
+        assert_eq!(voting.candidates[2].votes, vec![0, 1]);
+
+        voting.disable_vote(0);
+        assert!(!voting.edit_vote(0));
+    }
+
+    #[test]
+    fn edit_vote_keeps_invalid_slots() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "nobody".to_string();
+        voting.candidate_selections[1].search_text = "ok".to_string();
+        voting.vote();
+        voting.candidate_selections[0].search_text = "nobody".to_string();
+        voting.vote();
+
+        let papers = voting
+            .papers
+            .iter()
+            .map(|paper| (paper.voting.clone(), paper.invalid, paper.blank))
+            .collect::<Vec<_>>();
+        let tallies = voting
+            .candidates
+            .iter()
+            .map(|candidate| candidate.votes.clone())
+            .collect::<Vec<_>>();
+
+        for index in 0..2 {
+            assert!(voting.edit_vote(index));
+            voting.vote();
+        }
+
+        assert_eq!(
+            voting
+                .papers
+                .iter()
+                .map(|paper| (paper.voting.clone(), paper.invalid, paper.blank))
+                .collect::<Vec<_>>(),
+            papers
+        );
+        assert_eq!(
+            voting
+                .candidates
+                .iter()
+                .map(|candidate| candidate.votes.clone())
+                .collect::<Vec<_>>(),
+            tallies
+        );
+        assert_eq!(voting.invalid(), 1);
+        assert_eq!(voting.blank(), 0);
+    }
+
+    #[test]
+    fn rejections() {
+        let mut voting = Voting::new(Vec::from(get_candidates(1)), "test.txt", 1).unwrap();
+
+        voting.candidate_selections[0].search_text = "nobody".to_string();
+        voting.vote();
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.vote();
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.vote();
+
+        assert!(!voting.set_reason(1, Some(Reason::Illegible)));
+
+        voting.disable_vote(2);
+        assert!(voting.set_reason(0, Some(Reason::Illegible)));
+        assert!(voting.set_reason(2, Some(Reason::EnteredTwice)));
+
+        assert_eq!(
+            voting.rejections().into_iter().collect::<Vec<_>>(),
+            vec![
+                (Some(Reason::Illegible), 1),
+                (Some(Reason::EnteredTwice), 1)
+            ]
+        );
+
+        voting.enable_vote(2);
+        assert_eq!(voting.papers[2].reason, None);
+    }
+
+    #[test]
+    fn vote_blank() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        assert!(voting.vote_blank());
+        voting.vote();
+
+        voting.candidate_selections[0].search_text = "nobody".to_string();
+        voting.vote();
+
+        assert_eq!(voting.blank(), 2);
+        assert_eq!(voting.invalid(), 1);
+        assert_eq!(voting.candidates[1].votes, vec![0, 0]);
+        assert_eq!(voting.counted_papers().count(), 0);
+
+        voting.disable_vote(0);
+        assert_eq!(voting.blank(), 1);
+    }
+
+    #[test]
+    fn vote_blank_keeps_the_corrected_paper() {
+        let mut voting = Voting::new(Vec::from(get_candidates(2)), "test.txt", 2).unwrap();
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.vote();
+
+        assert!(voting.edit_vote(0));
+        assert!(!voting.vote_blank());
+
+        assert_eq!(voting.editing, Some(0));
+        assert_eq!(voting.candidate_selections[0].search_text, "test");
+        assert_eq!(voting.papers.len(), 1);
+        assert_eq!(voting.blank(), 0);
+        assert_eq!(voting.candidates[1].votes, vec![1, 0]);
+
+        voting.cancel_edit();
+        assert!(voting.vote_blank());
+        assert_eq!(voting.blank(), 1);
+    }
+
+    #[test]
+    fn vote_follows_validity() {
+        let mut voting = Voting::new(Vec::from(get_candidates(3)), "test.txt", 3).unwrap();
+        voting.validity = Validity::UpToFirstError;
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.candidate_selections[1].search_text = "nobody".to_string();
+        voting.candidate_selections[2].search_text = "ok".to_string();
+        voting.vote();
+
+        assert_eq!(
+            voting.papers[0].voting.ranking(),
+            [
+                Preference::Candidate(1),
+                Preference::Invalid,
+                Preference::Invalid
+            ]
+        );
+        assert_eq!(voting.candidates[2].votes, vec![0, 0, 0]);
+
+        voting.validity = Validity::VoidOnError;
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.candidate_selections[1].search_text = "nobody".to_string();
+        voting.vote();
+
+        assert_eq!(voting.invalid(), 1);
+        assert_eq!(voting.candidates[1].votes, vec![1, 0, 0]);
+
+        // a repeated candidate voids both of its slots unless the first pick stands
+        for validity in [Validity::AnyValid, Validity::FirstPickStands] {
+            voting.validity = validity;
+
+            voting.candidate_selections[0].search_text = "test".to_string();
+            voting.candidate_selections[1].search_text = "test".to_string();
+            voting.candidate_selections[2].search_text = "ok".to_string();
+            voting.vote();
+        }
+
+        assert_eq!(
+            voting.papers[2].voting.ranking(),
+            [
+                Preference::Invalid,
+                Preference::Invalid,
+                Preference::Candidate(2)
+            ]
+        );
+        assert_eq!(
+            voting.papers[3].voting.ranking(),
+            [
+                Preference::Candidate(1),
+                Preference::Invalid,
+                Preference::Candidate(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn batches() {
+        let mut voting = Voting::new(Vec::from(get_candidates(1)), "test.txt", 1).unwrap();
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.vote();
+
+        voting.batch = Some("room 2".to_string());
+        voting.candidate_selections[0].search_text = "ok".to_string();
+        voting.vote();
+        voting.vote();
+
+        voting.edit_vote(0);
+        voting.candidate_selections[0].select("time test", &voting.candidates);
+        voting.vote();
+
+        let serials: Vec<usize> = voting.papers.iter().map(|paper| paper.serial).collect();
+        assert_eq!(serials, vec![1, 2, 3]);
+        assert_eq!(voting.papers[0].batch, None);
+
+        assert_eq!(voting.batches(), vec![None, Some("room 2".to_string())]);
+
+        let room = voting.batch_voting(&Some("room 2".to_string()));
+        assert_eq!(room.papers.len(), 2);
+        assert_eq!(room.blank(), 1);
+        assert_eq!(room.candidates[2].votes, vec![1]);
+        assert_eq!(room.candidates[0].votes, vec![0]);
+    }
+
+    #[test]
+    fn rename_keeps_papers() {
+        let mut voting = Voting::new(Vec::from(get_candidates(1)), "test.txt", 1).unwrap();
+
+        voting.candidate_selections[0].search_text = "test".to_string();
+        voting.vote();
+
+        voting.rename("test", "renamed").unwrap();
+
+        assert_eq!(voting.candidates[1].name, "renamed");
+        assert_eq!(voting.candidates[1].get_first_votes(), 1);
+        assert_eq!(voting.rankings(), vec![vec![1]]);
+        assert!(voting.rename("renamed", "ok i think").is_err());
+        assert!(voting.rename("gone", "new").is_err());
+    }
 }
@@ -0,0 +1,431 @@
+use crate::voting::counting::{elect_top, CountResult, Round};
+use crate::voting::Voting;
+use anyhow::{anyhow, bail};
+use iter_tools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// how tied candidates are ordered - inside a count and in the final ranking
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum TieBreak {
+    /// ties are left standing and reported
+    #[default]
+    None,
+    /// more first votes wins, then more second votes...
+    LaterRanks,
+    /// the better tally in the latest round where the tied candidates differ
+    PreviousRound,
+    /// a seeded draw - the same seed always draws the same order
+    Lots(u64),
+    /// the chair decides when the result is shown
+    Chair,
+}
+
+impl FromStr for TieBreak {
+    type Err = anyhow::Error;
+
+    /// `none`, `later-ranks`, `previous-round`, `lots`, `lots:1234` or `chair`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_lowercase();
+
+        if let Some(seed) = text.strip_prefix("lots:") {
+            let seed = seed
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| anyhow!("invalid seed - {}", seed))?;
+
+            return Ok(TieBreak::Lots(seed));
+        }
+
+        Ok(match text.as_str() {
+            "none" => TieBreak::None,
+            "later-ranks" => TieBreak::LaterRanks,
+            "previous-round" => TieBreak::PreviousRound,
+            "lots" => TieBreak::Lots(new_seed()),
+            "chair" => TieBreak::Chair,
+            _ => bail!("unknown tie-break - {}", text),
+        })
+    }
+}
+
+impl Display for TieBreak {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TieBreak::None => write!(f, "none"),
+            TieBreak::LaterRanks => write!(f, "later-ranks"),
+            TieBreak::PreviousRound => write!(f, "previous-round"),
+            TieBreak::Lots(seed) => write!(f, "lots:{}", seed),
+            TieBreak::Chair => write!(f, "chair"),
+        }
+    }
+}
+
+fn new_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// splitmix64 - small, fast and the same on every platform
+struct Lots {
+    state: u64,
+}
+
+impl Lots {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    fn shuffle(&mut self, candidates: &mut [usize]) {
+        for index in (1..candidates.len()).rev() {
+            let other = (self.next() % (index as u64 + 1)) as usize;
+            candidates.swap(index, other);
+        }
+    }
+}
+
+/// splits a tied group into smaller groups by a key - greater keys first
+fn split_by<K: PartialOrd>(group: &[usize], key: impl Fn(usize) -> K) -> Vec<Vec<usize>> {
+    group
+        .iter()
+        .copied()
+        .sorted_by(|a, b| key(*b).partial_cmp(&key(*a)).unwrap_or(Ordering::Equal))
+        .group_by(|candidate| key(*candidate))
+        .into_iter()
+        .map(|(_, group)| group.collect())
+        .collect()
+}
+
+/// the tallies of the candidates from the last round back to the first,
+/// skipping rounds not every one of them took part in
+fn previous_rounds(rounds: &[Vec<(usize, f64)>], group: &[usize], candidate: usize) -> Vec<f64> {
+    rounds
+        .iter()
+        .rev()
+        .filter(|tallies| {
+            group
+                .iter()
+                .all(|member| tallies.iter().any(|(other, _)| other == member))
+        })
+        .filter_map(|tallies| {
+            tallies
+                .iter()
+                .find(|(other, _)| *other == candidate)
+                .map(|(_, value)| *value)
+        })
+        .collect()
+}
+
+/// decides a tie inside a count - gets the tied candidates and the tallies of the rounds
+/// counted so far and returns them split best first with a note, one group is still tied
+pub type Ties<'a> = dyn FnMut(&[usize], &[Vec<(usize, f64)>]) -> (Vec<Vec<usize>>, String) + 'a;
+
+/// the candidate a decision puts last - `None` while the last place is still tied
+pub fn last_place(order: &[Vec<usize>]) -> Option<usize> {
+    match order.last() {
+        Some(group) if group.len() == 1 => Some(group[0]),
+        _ => None,
+    }
+}
+
+/// gets a tied group and returns it split into the order the chair decided
+pub type Chair<'a> = dyn FnMut(&[usize]) -> Vec<Vec<usize>> + 'a;
+
+/// applies the policy of the voting to tied candidates - the lots of one count are
+/// drawn from the same seed one after the other
+pub struct TieBreaker<'a> {
+    voting: &'a Voting,
+    lots: Option<Lots>,
+    chair: Option<&'a mut Chair<'a>>,
+}
+
+impl<'a> TieBreaker<'a> {
+    /// never asks the chair - ties left to the chair stay
+    pub fn new(voting: &'a Voting) -> TieBreaker<'a> {
+        TieBreaker {
+            voting,
+            lots: match voting.tie_break {
+                TieBreak::Lots(seed) => Some(Lots { state: seed }),
+                _ => None,
+            },
+            chair: None,
+        }
+    }
+
+    pub fn with_chair(voting: &'a Voting, chair: &'a mut Chair<'a>) -> TieBreaker<'a> {
+        TieBreaker {
+            chair: Some(chair),
+            ..TieBreaker::new(voting)
+        }
+    }
+
+    /// splits a tied group best first and describes the decision,
+    /// `previous` are the tallies of the rounds counted so far
+    pub fn order(
+        &mut self,
+        group: &[usize],
+        previous: &[Vec<(usize, f64)>],
+    ) -> (Vec<Vec<usize>>, String) {
+        let voting = self.voting;
+
+        let groups: Vec<Vec<usize>> = match voting.tie_break {
+            TieBreak::None => vec![group.to_vec()],
+            TieBreak::LaterRanks => split_by(group, |candidate| {
+                voting.candidates[candidate].votes.clone()
+            }),
+            TieBreak::PreviousRound => split_by(group, |candidate| {
+                previous_rounds(previous, group, candidate)
+            }),
+            TieBreak::Lots(_) => {
+                let mut order = group.to_vec();
+                self.lots.as_mut().unwrap().shuffle(&mut order);
+                order.into_iter().map(|candidate| vec![candidate]).collect()
+            }
+            TieBreak::Chair => match &mut self.chair {
+                Some(chair) => chair(group),
+                None => vec![group.to_vec()],
+            },
+        };
+
+        let names = |group: &[usize]| {
+            group
+                .iter()
+                .map(|candidate| &voting.candidates[*candidate].name)
+                .join(", ")
+        };
+
+        let decision = if groups.len() == 1 {
+            "still tied".to_string()
+        } else {
+            groups.iter().map(|group| names(group)).join(" before ")
+        };
+
+        let note = format!("{} tied - {}: {}", names(group), voting.tie_break, decision);
+
+        (groups, note)
+    }
+}
+
+/// orders tied groups of the ranking by the policy of the voting - every decision is
+/// added as a note of a final "Tie-breaks" round
+pub fn break_ties(result: CountResult, ties: &mut TieBreaker) -> CountResult {
+    let previous: Vec<Vec<(usize, f64)>> = result
+        .rounds
+        .iter()
+        .map(|round| round.tallies.clone())
+        .collect();
+
+    let mut notes = vec![];
+    let mut ranking = vec![];
+
+    for group in &result.ranking {
+        if group.len() < 2 {
+            ranking.push(group.clone());
+            continue;
+        }
+
+        let (groups, note) = ties.order(group, &previous);
+
+        notes.push(note);
+        ranking.extend(groups);
+    }
+
+    if notes.is_empty() {
+        return result;
+    }
+
+    let seats = ties.voting.seats;
+    let elected = if result.elected.len() < seats {
+        elect_top(&ranking, seats)
+    } else {
+        result.elected
+    };
+
+    let mut rounds = result.rounds;
+    rounds.push(Round {
+        title: "Tie-breaks".to_string(),
+        notes,
+        ..Round::default()
+    });
+
+    CountResult {
+        rounds,
+        ranking,
+        elected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::candidate::Candidate;
+    use crate::voting::counting::{CountResult, Round};
+    use crate::voting::tie_break::{break_ties, TieBreak, TieBreaker};
+    use crate::voting::Voting;
+
+    fn get_voting(tie_break: TieBreak) -> Voting {
+        let mut candidates = vec![
+            Candidate::new("huff".to_string(), 2),
+            Candidate::new("puff".to_string(), 2),
+            Candidate::new("muff".to_string(), 2),
+        ];
+        candidates[1].votes = vec![1, 3];
+        candidates[2].votes = vec![1, 1];
+
+        let mut voting = Voting::new(candidates, "test.txt", 2).unwrap();
+        voting.tie_break = tie_break;
+        voting
+    }
+
+    fn tied_result() -> CountResult {
+        CountResult {
+            rounds: vec![
+                Round {
+                    tallies: vec![(0, 1.0), (1, 4.0), (2, 2.0)],
+                    ..Round::default()
+                },
+                Round {
+                    tallies: vec![(1, 5.0), (2, 5.0)],
+                    ..Round::default()
+                },
+            ],
+            ranking: vec![vec![1, 2], vec![0]],
+            elected: vec![],
+        }
+    }
+
+    fn no_chair(_: &[usize]) -> Vec<Vec<usize>> {
+        panic!("the chair was asked")
+    }
+
+    #[test]
+    fn parse_tie_break() {
+        for tie_break in [
+            TieBreak::None,
+            TieBreak::LaterRanks,
+            TieBreak::PreviousRound,
+            TieBreak::Lots(42),
+            TieBreak::Chair,
+        ] {
+            assert_eq!(
+                tie_break.to_string().parse::<TieBreak>().unwrap(),
+                tie_break
+            );
+        }
+
+        assert!(matches!("lots".parse::<TieBreak>(), Ok(TieBreak::Lots(_))));
+        assert!("lots:huff".parse::<TieBreak>().is_err());
+    }
+
+    #[test]
+    fn none_reports_ties() {
+        let voting = get_voting(TieBreak::None);
+
+        let result = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut no_chair),
+        );
+
+        assert_eq!(result.ranking, vec![vec![1, 2], vec![0]]);
+        assert!(result.elected.is_empty());
+        assert_eq!(
+            result.rounds.last().unwrap().notes,
+            vec!["puff, muff tied - none: still tied"]
+        );
+    }
+
+    #[test]
+    fn later_ranks() {
+        let voting = get_voting(TieBreak::LaterRanks);
+
+        let result = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut no_chair),
+        );
+
+        assert_eq!(result.ranking, vec![vec![1], vec![2], vec![0]]);
+        assert_eq!(result.elected, vec![1]);
+        assert_eq!(
+            result.rounds.last().unwrap().notes,
+            vec!["puff, muff tied - later-ranks: puff before muff"]
+        );
+    }
+
+    #[test]
+    fn previous_round() {
+        let voting = get_voting(TieBreak::PreviousRound);
+
+        let result = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut no_chair),
+        );
+
+        assert_eq!(result.ranking, vec![vec![1], vec![2], vec![0]]);
+    }
+
+    #[test]
+    fn lots_repeat_with_the_seed() {
+        let voting = get_voting(TieBreak::Lots(7));
+
+        let first = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut no_chair),
+        );
+        let second = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut no_chair),
+        );
+
+        assert_eq!(first, second);
+        assert_eq!(first.ranking.len(), 3);
+        assert_eq!(first.elected.len(), 1);
+    }
+
+    #[test]
+    fn chair_decides() {
+        let voting = get_voting(TieBreak::Chair);
+
+        let mut chair = |group: &[usize]| {
+            group
+                .iter()
+                .rev()
+                .map(|candidate| vec![*candidate])
+                .collect()
+        };
+
+        let result = break_ties(
+            tied_result(),
+            &mut TieBreaker::with_chair(&voting, &mut chair),
+        );
+
+        assert_eq!(result.ranking, vec![vec![2], vec![1], vec![0]]);
+        assert_eq!(result.elected, vec![2]);
+    }
+
+    #[test]
+    fn untied_results_stay() {
+        let voting = get_voting(TieBreak::Chair);
+
+        let mut result = tied_result();
+        result.ranking = vec![vec![1], vec![2], vec![0]];
+        result.elected = vec![1];
+
+        let expected = CountResult {
+            rounds: tied_result().rounds,
+            ranking: result.ranking.clone(),
+            elected: result.elected.clone(),
+        };
+
+        assert_eq!(
+            break_ties(result, &mut TieBreaker::with_chair(&voting, &mut no_chair)),
+            expected
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// which slots of a ranked paper count when some of them are faulty
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Validity {
+    /// faulty slots are ignored, the paper counts while one slot is valid
+    #[default]
+    AnyValid,
+    /// one faulty slot voids the whole paper
+    VoidOnError,
+    /// only the preferences before the first faulty slot count
+    UpToFirstError,
+    /// every rank has to be filled with a candidate
+    AllRanks,
+    /// an empty rank followed by a filled one voids the paper
+    NoSkippedRanks,
+    /// like `AnyValid`, but of a repeated candidate the first pick counts
+    FirstPickStands,
+}
+
+/// what was entered into one ranked slot
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Mark {
+    Candidate,
+    Blank,
+    /// the text matches no candidate
+    Unmatched,
+    /// the candidate already is on an earlier slot
+    Repeated,
+    /// the candidate is picked again on a later slot
+    RepeatedLater,
+}
+
+impl Mark {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Mark::Unmatched | Mark::Repeated | Mark::RepeatedLater)
+    }
+
+    fn problem(&self) -> &'static str {
+        match self {
+            Mark::Candidate => "is fine",
+            Mark::Blank => "is empty",
+            Mark::Unmatched => "matches no candidate",
+            Mark::Repeated => "repeats an earlier preference",
+            Mark::RepeatedLater => "is repeated on a later rank",
+        }
+    }
+}
+
+/// the outcome of applying a `Validity` to the slots of one paper
+#[derive(PartialEq, Debug)]
+pub struct Decision {
+    /// whether each slot is counted
+    pub counted: Vec<bool>,
+    /// slots that break the rule or are dropped because of it
+    pub faulty: Vec<bool>,
+    /// why slots or the paper do not count - `None` when everything marked counts
+    pub explanation: Option<String>,
+}
+
+impl Decision {
+    /// the paper counts if any slot does
+    pub fn is_valid(&self) -> bool {
+        self.counted.iter().any(|counted| *counted)
+    }
+}
+
+impl Validity {
+    pub fn decide(&self, marks: &[Mark]) -> Decision {
+        // the first pick of a repeated candidate stands
+        let marks: Vec<Mark> = marks
+            .iter()
+            .map(|mark| match (self, mark) {
+                (Validity::FirstPickStands, Mark::RepeatedLater) => Mark::Candidate,
+                (_, mark) => *mark,
+            })
+            .collect();
+
+        let filled = |index: usize| marks[index] == Mark::Candidate;
+        let first_error = marks.iter().position(Mark::is_error);
+
+        let mut counted: Vec<bool> = (0..marks.len()).map(filled).collect();
+        let mut faulty: Vec<bool> = marks.iter().map(Mark::is_error).collect();
+
+        let explain = |index: usize, consequence: &str| {
+            Some(format!(
+                "rank {} {} - {}",
+                index + 1,
+                marks[index].problem(),
+                consequence
+            ))
+        };
+
+        let explanation = match self {
+            Validity::AnyValid | Validity::FirstPickStands => {
+                first_error.and_then(|index| explain(index, "the slot is ignored"))
+            }
+            Validity::VoidOnError => first_error.and_then(|index| {
+                counted.fill(false);
+                explain(index, "the whole paper is void")
+            }),
+            Validity::UpToFirstError => first_error.and_then(|error| {
+                for index in error..marks.len() {
+                    faulty[index] |= filled(index);
+                    counted[index] = false;
+                }
+
+                explain(error, "only the preferences before it count")
+            }),
+            Validity::AllRanks => {
+                let unfilled = marks.iter().position(|mark| *mark != Mark::Candidate);
+
+                match unfilled {
+                    Some(index) if marks.iter().any(|mark| *mark != Mark::Blank) => {
+                        for (slot, mark) in marks.iter().enumerate() {
+                            faulty[slot] = *mark != Mark::Candidate;
+                        }
+
+                        counted.fill(false);
+                        explain(index, "every rank has to be filled")
+                    }
+                    _ => None,
+                }
+            }
+            Validity::NoSkippedRanks => {
+                let skipped = marks.iter().enumerate().position(|(index, mark)| {
+                    *mark == Mark::Blank && marks[index..].contains(&Mark::Candidate)
+                });
+
+                match skipped {
+                    Some(index) => {
+                        faulty[index] = true;
+                        counted.fill(false);
+                        explain(index, "ranks can not be skipped, the whole paper is void")
+                    }
+                    None => first_error.and_then(|index| explain(index, "the slot is ignored")),
+                }
+            }
+        };
+
+        Decision {
+            counted,
+            faulty,
+            explanation,
+        }
+    }
+}
+
+impl FromStr for Validity {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(match text.trim().to_lowercase().as_str() {
+            "any-valid" => Validity::AnyValid,
+            "void-on-error" => Validity::VoidOnError,
+            "up-to-first-error" => Validity::UpToFirstError,
+            "all-ranks" => Validity::AllRanks,
+            "no-skipped-ranks" => Validity::NoSkippedRanks,
+            "first-pick-stands" => Validity::FirstPickStands,
+            text => bail!("unknown validity - {}", text),
+        })
+    }
+}
+
+impl Display for Validity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Validity::AnyValid => write!(f, "any-valid"),
+            Validity::VoidOnError => write!(f, "void-on-error"),
+            Validity::UpToFirstError => write!(f, "up-to-first-error"),
+            Validity::AllRanks => write!(f, "all-ranks"),
+            Validity::NoSkippedRanks => write!(f, "no-skipped-ranks"),
+            Validity::FirstPickStands => write!(f, "first-pick-stands"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::validity::{Mark, Validity};
+
+    const MARKS: [Mark; 4] = [
+        Mark::Candidate,
+        Mark::Repeated,
+        Mark::Candidate,
+        Mark::Blank,
+    ];
+
+    #[test]
+    fn any_valid() {
+        let decision = Validity::AnyValid.decide(&MARKS);
+
+        assert_eq!(decision.counted, vec![true, false, true, false]);
+        assert_eq!(decision.faulty, vec![false, true, false, false]);
+        assert_eq!(
+            decision.explanation.unwrap(),
+            "rank 2 repeats an earlier preference - the slot is ignored"
+        );
+    }
+
+    #[test]
+    fn void_on_error() {
+        let decision = Validity::VoidOnError.decide(&MARKS);
+
+        assert!(!decision.is_valid());
+        assert!(Validity::VoidOnError
+            .decide(&[Mark::Candidate, Mark::Blank])
+            .is_valid());
+    }
+
+    #[test]
+    fn up_to_first_error() {
+        let decision = Validity::UpToFirstError.decide(&MARKS);
+
+        assert_eq!(decision.counted, vec![true, false, false, false]);
+        assert_eq!(decision.faulty, vec![false, true, true, false]);
+    }
+
+    #[test]
+    fn all_ranks() {
+        assert!(!Validity::AllRanks.decide(&MARKS).is_valid());
+        assert!(Validity::AllRanks
+            .decide(&[Mark::Candidate, Mark::Candidate])
+            .is_valid());
+
+        // nothing entered yet is not a fault
+        let decision = Validity::AllRanks.decide(&[Mark::Blank, Mark::Blank]);
+        assert_eq!(decision.faulty, vec![false, false]);
+        assert_eq!(decision.explanation, None);
+    }
+
+    #[test]
+    fn no_skipped_ranks() {
+        let decision = Validity::NoSkippedRanks.decide(&[Mark::Blank, Mark::Candidate]);
+
+        assert!(!decision.is_valid());
+        assert_eq!(decision.faulty, vec![true, false]);
+
+        assert!(Validity::NoSkippedRanks
+            .decide(&[Mark::Candidate, Mark::Blank])
+            .is_valid());
+    }
+
+    #[test]
+    fn first_pick_stands() {
+        let marks = [Mark::RepeatedLater, Mark::Repeated, Mark::Candidate];
+
+        let decision = Validity::AnyValid.decide(&marks);
+        assert_eq!(decision.counted, vec![false, false, true]);
+        assert_eq!(
+            decision.explanation.unwrap(),
+            "rank 1 is repeated on a later rank - the slot is ignored"
+        );
+
+        let decision = Validity::FirstPickStands.decide(&marks);
+        assert_eq!(decision.counted, vec![true, false, true]);
+        assert_eq!(decision.faulty, vec![false, true, false]);
+    }
+
+    #[test]
+    fn parse() {
+        for validity in [
+            Validity::AnyValid,
+            Validity::VoidOnError,
+            Validity::UpToFirstError,
+            Validity::AllRanks,
+            Validity::NoSkippedRanks,
+            Validity::FirstPickStands,
+        ] {
+            assert_eq!(validity.to_string().parse::<Validity>().unwrap(), validity);
+        }
+
+        assert!("lenient".parse::<Validity>().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{anyhow, bail};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// how many points a ranking position is worth
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Default)]
+pub enum Weighting {
+    /// `allowed votes - position` - a first choice out of 3 is worth 3
+    #[default]
+    Borda,
+    /// `1 / (position + 1)` - 1, 1/2, 1/3...
+    Dowdall,
+    /// `filled ranks - position` - only ranking one candidate gives them 1 point
+    ModifiedBorda,
+    /// points per position, positions beyond the list are worth nothing
+    Custom(Vec<f64>),
+    /// only first choices count
+    Plurality,
+}
+
+impl Weighting {
+    /// points for `position` on a paper with `filled` of `allowed_votes` ranks filled in
+    pub fn points(&self, position: usize, filled: usize, allowed_votes: usize) -> f64 {
+        match self {
+            Weighting::Borda => allowed_votes.saturating_sub(position) as f64,
+            Weighting::Dowdall => 1.0 / (position + 1) as f64,
+            Weighting::ModifiedBorda => filled.saturating_sub(position) as f64,
+            Weighting::Custom(points) => points.get(position).copied().unwrap_or(0.0),
+            Weighting::Plurality => {
+                if position == 0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Weighting {
+    type Err = anyhow::Error;
+
+    /// `borda`, `dowdall`, `modified-borda`, `plurality` or `custom:12,10,8`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_lowercase();
+
+        if let Some(points) = text.strip_prefix("custom:") {
+            let points = points
+                .split(',')
+                .map(|point| {
+                    point
+                        .trim()
+                        .parse::<f64>()
+                        .map_err(|_| anyhow!("invalid custom weight - {}", point))
+                })
+                .collect::<anyhow::Result<Vec<f64>>>()?;
+
+            return Ok(Weighting::Custom(points));
+        }
+
+        Ok(match text.as_str() {
+            "borda" => Weighting::Borda,
+            "dowdall" => Weighting::Dowdall,
+            "modified-borda" => Weighting::ModifiedBorda,
+            "plurality" => Weighting::Plurality,
+            _ => bail!("unknown weighting - {}", text),
+        })
+    }
+}
+
+impl Display for Weighting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Weighting::Borda => write!(f, "borda"),
+            Weighting::Dowdall => write!(f, "dowdall"),
+            Weighting::ModifiedBorda => write!(f, "modified-borda"),
+            Weighting::Custom(points) => {
+                let points: Vec<String> = points.iter().map(|point| point.to_string()).collect();
+                write!(f, "custom:{}", points.join(","))
+            }
+            Weighting::Plurality => write!(f, "plurality"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::voting::weighting::Weighting;
+
+    #[test]
+    fn points() {
+        assert_eq!(Weighting::Borda.points(0, 1, 3), 3.0);
+        assert_eq!(Weighting::Borda.points(2, 3, 3), 1.0);
+
+        assert_eq!(Weighting::Dowdall.points(1, 3, 3), 0.5);
+
+        assert_eq!(Weighting::ModifiedBorda.points(0, 1, 3), 1.0);
+        assert_eq!(Weighting::ModifiedBorda.points(0, 3, 3), 3.0);
+
+        assert_eq!(Weighting::Custom(vec![12.0, 10.0]).points(1, 3, 3), 10.0);
+        assert_eq!(Weighting::Custom(vec![12.0, 10.0]).points(2, 3, 3), 0.0);
+
+        assert_eq!(Weighting::Plurality.points(0, 3, 3), 1.0);
+        assert_eq!(Weighting::Plurality.points(1, 3, 3), 0.0);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("Borda".parse::<Weighting>().unwrap(), Weighting::Borda);
+        assert_eq!(
+            "modified-borda".parse::<Weighting>().unwrap(),
+            Weighting::ModifiedBorda
+        );
+        assert_eq!(
+            "custom:12, 10,8".parse::<Weighting>().unwrap(),
+            Weighting::Custom(vec![12.0, 10.0, 8.0])
+        );
+
+        assert!("custom:12,a".parse::<Weighting>().is_err());
+        assert!("huff".parse::<Weighting>().is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let weighting = Weighting::Custom(vec![12.0, 10.0, 8.5]);
+
+        assert_eq!(
+            weighting.to_string().parse::<Weighting>().unwrap(),
+            weighting
+        );
+    }
+}